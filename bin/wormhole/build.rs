@@ -1,7 +1,49 @@
+use std::{env, fs, path::Path, process::Command};
+
+/// The file name of the Pico program ELF written to the `elf` directory of the program.
+const PICO_ELF: &str = "riscv32im-pico-zkvm-elf";
+
 fn main() {
-    // Build SP1 program
+    // Build SP1 programs
     sp1_build::build_program_with_args("../../programs/sp1", Default::default());
+    sp1_build::build_program_with_args("../../programs/sp1-aggregator", Default::default());
 
-    // Build Risc0 program
+    // Build Risc0 programs
     risc0_build::embed_methods();
+
+    // Build Pico program
+    build_pico_program("../../programs/pico");
+}
+
+/// Builds the Pico program with `cargo pico build` and copies the ELF to `OUT_DIR`.
+///
+/// The build is skipped if `PICO_SKIP_PROGRAM_BUILD` is set, similar to `SP1_SKIP_PROGRAM_BUILD`
+/// and `RISC0_SKIP_BUILD`. An empty ELF is written in its place, so that the other backends can
+/// still be used.
+fn build_pico_program(path: &str) {
+    println!("cargo:rerun-if-env-changed=PICO_SKIP_PROGRAM_BUILD");
+    println!("cargo:rerun-if-changed={path}/src");
+    println!("cargo:rerun-if-changed={path}/Cargo.toml");
+    println!("cargo:rerun-if-changed=../../crates/program-core/src");
+
+    let out_elf = Path::new(&env::var_os("OUT_DIR").expect("OUT_DIR is not set")).join(PICO_ELF);
+    if env::var_os("PICO_SKIP_PROGRAM_BUILD").is_some() {
+        println!("cargo:warning=Skipping the Pico program build, PICO_SKIP_PROGRAM_BUILD is set");
+        fs::write(&out_elf, []).expect("failed to write the Pico ELF");
+        return;
+    }
+
+    let status = Command::new("cargo")
+        .args(["pico", "build"])
+        .current_dir(path)
+        // Do not leak the host build configuration into the program build.
+        .env_remove("RUSTC")
+        .env_remove("RUSTC_WRAPPER")
+        .env_remove("CARGO_ENCODED_RUSTFLAGS")
+        .env_remove("CARGO_TARGET_DIR")
+        .status()
+        .expect("failed to run `cargo pico build`, set PICO_SKIP_PROGRAM_BUILD to skip it");
+    assert!(status.success(), "`cargo pico build` failed: {status}");
+    fs::copy(Path::new(path).join("elf").join(PICO_ELF), &out_elf)
+        .expect("failed to copy the Pico ELF");
 }
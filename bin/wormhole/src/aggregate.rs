@@ -0,0 +1,67 @@
+use crate::{batch_input::read_program_input, risc0, sp1, withdraw::Backend};
+use alloy_primitives::{Bytes, B256};
+use alloy_wormhole::{WormholeAggregationOutput, WormholeProofSystem};
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+use std::{fs, path::PathBuf};
+
+#[derive(Parser, Debug)]
+pub struct AggregateCommand {
+    /// The paths to the program inputs to prove and aggregate.
+    #[clap(long = "input", required = true, num_args = 1..)]
+    inputs: Vec<PathBuf>,
+
+    /// The zkVM backend to generate the proofs with.
+    #[clap(long, value_enum, default_value_t = Backend::Sp1)]
+    backend: Backend,
+
+    /// The optional path to write the aggregation proof to.
+    #[clap(long)]
+    out: Option<PathBuf>,
+}
+
+impl AggregateCommand {
+    pub fn run(self) -> anyhow::Result<()> {
+        let inputs = self
+            .inputs
+            .iter()
+            .map(|path| read_program_input(path))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let proof = match self.backend {
+            Backend::Sp1 => sp1::aggregate_wormhole_proofs(&inputs)?,
+            Backend::Risc0 => risc0::aggregate_wormhole_proofs(&inputs)?,
+        };
+        println!(
+            "Aggregated {} proofs of program {} into {} proof of program {}",
+            proof.output.outputs.len(),
+            proof.output.program_id,
+            proof.proof_system,
+            proof.program_id
+        );
+        for output in &proof.output.outputs {
+            for entry in &output.nullifiers {
+                println!("Nullifier: {}", entry.nullifier);
+            }
+        }
+
+        if let Some(out) = self.out {
+            fs::write(out, serde_json::to_string_pretty(&proof)?)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The proof of the aggregation program alongside its output.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct WormholeAggregationProof {
+    /// The proof system that produced the proof.
+    pub proof_system: WormholeProofSystem,
+    /// The commitment to the aggregation program.
+    pub program_id: B256,
+    /// The output of the aggregation program.
+    pub output: WormholeAggregationOutput,
+    /// The ZK proof of the aggregation program execution.
+    pub proof: Bytes,
+}
@@ -0,0 +1,73 @@
+use crate::create_input::WormholeProgramInputExt;
+use alloy_primitives::B256;
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use wormhole_program_core::{WormholeBatchProgramInput, WormholeProgramInput};
+
+#[derive(Parser, Debug)]
+pub struct CreateBatchInputCommand {
+    /// The paths to the program inputs created with `create-input` at the same block.
+    #[clap(long = "input", required = true, num_args = 1..)]
+    pub inputs: Vec<PathBuf>,
+}
+
+impl CreateBatchInputCommand {
+    pub fn run(self) -> anyhow::Result<()> {
+        let mut block = None;
+        let mut inputs = Vec::with_capacity(self.inputs.len());
+        for path in &self.inputs {
+            let input: WormholeProgramInputExt = serde_json::from_slice(&fs::read(path)?)?;
+            let input_block = (input.block_number, input.block_hash);
+            if *block.get_or_insert(input_block) != input_block {
+                anyhow::bail!(
+                    "input {} was created at block {} ({}), expected all inputs at the same block",
+                    path.display(),
+                    input.block_number,
+                    input.block_hash
+                );
+            }
+            inputs.push(input.inner);
+        }
+
+        let (block_number, block_hash) = block.unwrap_or_default();
+        let batch = WormholeBatchProgramInputExt {
+            inner: WormholeBatchProgramInput::from_inputs(inputs)?,
+            block_number,
+            block_hash,
+        };
+        println!("{}", serde_json::to_string_pretty(&batch)?);
+        Ok(())
+    }
+}
+
+/// Wormhole batch program input extended with additional information.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct WormholeBatchProgramInputExt {
+    /// Batch program input.
+    #[serde(flatten)]
+    pub inner: WormholeBatchProgramInput,
+    /// The block number proofs were generated at.
+    pub block_number: u64,
+    /// The block hash proofs were generated at.
+    pub block_hash: B256,
+}
+
+/// The program input file created with either `create-input` or `create-batch-input`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ProgramInputFile {
+    Batch(WormholeBatchProgramInput),
+    Single(WormholeProgramInput),
+}
+
+/// Reads the program input from the file. The single withdrawal input is read as a batch of one.
+pub fn read_program_input(path: &Path) -> anyhow::Result<WormholeBatchProgramInput> {
+    Ok(match serde_json::from_slice(&fs::read(path)?)? {
+        ProgramInputFile::Batch(input) => input,
+        ProgramInputFile::Single(input) => input.into(),
+    })
+}
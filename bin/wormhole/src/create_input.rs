@@ -1,20 +1,45 @@
-use alloy_eips::{BlockId, BlockNumberOrTag};
-use alloy_primitives::{Address, Bytes, B256, U256};
-use alloy_provider::{network::Ethereum, Provider, RootProvider};
-use alloy_wormhole::WormholeSecret;
+use crate::{
+    export_proofs::{BlockArgs, PrivacyArgs, ResolvedBlock, WormholeProofBundle},
+    keystore::SecretArgs,
+    params::WormholeParamsArgs,
+};
+use alloy_eips::BlockId;
+use alloy_primitives::{Address, B256, U256};
+use alloy_provider::RootProvider;
+use alloy_wormhole::{
+    nullifier::{walk_nullifier_chain, NullifierChainError, NullifierChainState},
+    WormholeParams, WormholeSecret,
+};
 use clap::Parser;
 use serde::{Deserialize, Serialize};
+use std::{fs, ops::Range, path::PathBuf};
 use wormhole_program_core::{WormholeProgramError, WormholeProgramInput};
 
 #[derive(Parser, Debug)]
 pub struct CreateInputCommand {
-    /// The Wormhole secret.
-    #[clap(long)]
-    pub secret: Bytes,
+    #[clap(flatten)]
+    pub secret: SecretArgs,
 
     /// The node RPC URL.
-    #[clap(long)]
-    pub rpc_url: String,
+    #[clap(long, required_unless_present = "from_bundle")]
+    pub rpc_url: Option<String>,
+
+    #[clap(flatten)]
+    pub block: BlockArgs,
+
+    #[clap(flatten)]
+    pub privacy: PrivacyArgs,
+
+    /// The path to the proof bundle exported with `export-proofs`.
+    /// The input is created without the node access.
+    #[clap(long, conflicts_with_all = ["rpc_url", "block", "min_confirmations", "decoys", "local_only", "nullifier_window"])]
+    pub from_bundle: Option<PathBuf>,
+
+    /// The number of nullifiers of the secret queried at once, so that the RPC provider can't
+    /// tell the spent nullifiers from the first unspent one. Doubled until the window includes
+    /// an unspent nullifier.
+    #[clap(long, default_value_t = 16, value_parser = clap::value_parser!(u64).range(1..))]
+    pub nullifier_window: u64,
 
     /// The address of the nullifier contract.
     #[clap(long)]
@@ -24,84 +49,272 @@ pub struct CreateInputCommand {
     #[clap(long)]
     pub withdraw_amount: U256,
 
-    /// Withdrawal index.
+    /// The recipient of the withdrawn amount.
+    #[clap(long)]
+    pub recipient: Address,
+
+    /// The relayer submitting the withdrawal on behalf of the recipient.
+    #[clap(long)]
+    pub relayer: Option<Address>,
+
+    /// The fee paid to the relayer out of the withdraw amount.
+    #[clap(long, requires = "relayer")]
+    pub relayer_fee: Option<U256>,
+
+    /// Withdrawal index. Discovered from the nullifier chain, the provided value must match it.
     #[clap(long)]
     pub withdrawal_index: Option<U256>,
 
-    /// Cumulative withdrawn amount.
+    /// Cumulative withdrawn amount. Decrypted from the nullifier chain, the provided value must
+    /// match it. Required only if the amount is not encrypted on chain and the withdraw amounts
+    /// have more than 3 significant digits, e.g. 1.234 ether, so it cannot be recovered.
     #[clap(long)]
     pub cumulative_withdrawn_amount: Option<U256>,
+
+    #[clap(flatten)]
+    pub params: WormholeParamsArgs,
 }
 
 impl CreateInputCommand {
     pub async fn run(self) -> anyhow::Result<()> {
-        let secret = WormholeSecret::try_from(self.secret)
-            .map_err(|_| WormholeProgramError::InvalidSecret)?;
+        let input = self.create_input().await?;
+        println!("{}", serde_json::to_string_pretty(&input)?);
+        Ok(())
+    }
 
-        let provider = RootProvider::<Ethereum>::connect(&self.rpc_url).await?;
+    /// Fetches the proofs or reads them from the bundle and creates the program input.
+    pub async fn create_input(&self) -> anyhow::Result<WormholeProgramInputExt> {
+        let params = self.params.params();
+        let secret = self.secret.secret()?;
+        if !secret.has_valid_length() {
+            return Err(WormholeProgramError::InvalidSecretLength.into());
+        }
+        if !secret.is_valid_with_params(&params) {
+            return Err(WormholeProgramError::InvalidSecret.into());
+        }
 
-        let block_id = BlockId::Number(BlockNumberOrTag::Latest);
-        let block = provider.get_block(block_id).await?.ok_or(anyhow::anyhow!("unknown block"))?;
+        let deposit_address = secret.burn_address_with_params(&params);
+        let bundle = match (&self.from_bundle, &self.rpc_url) {
+            (Some(path), _) => {
+                let bundle: WormholeProofBundle = serde_json::from_slice(&fs::read(path)?)?;
+                if bundle.header.inner.hash_slow() != bundle.header.hash {
+                    anyhow::bail!(
+                        "bundle header does not match the block hash {}",
+                        bundle.header.hash
+                    );
+                }
+                if bundle.deposit_proof.address != deposit_address {
+                    anyhow::bail!(
+                        "bundle contains the proof of {} instead of the burn address {deposit_address}",
+                        bundle.deposit_proof.address
+                    );
+                }
+                if bundle.nullifier_proof.address != self.nullifier_address {
+                    anyhow::bail!(
+                        "bundle contains the proof of {} instead of the nullifier contract {}",
+                        bundle.nullifier_proof.address,
+                        self.nullifier_address
+                    );
+                }
+                bundle
+            }
+            (None, Some(rpc_url)) => {
+                let provider = self.privacy.connect(rpc_url).await?;
+                let block = self.block.resolve(&provider).await?;
+                self.fetch_bundle(&provider, &block, &secret, &params, deposit_address).await?
+            }
+            (None, None) => anyhow::bail!("either RPC URL or proof bundle must be provided"),
+        };
+        self.create_input_from_bundle(secret, params, bundle)
+    }
 
-        let deposit_address = secret.burn_address();
-        let deposit_proof =
-            provider.get_proof(deposit_address, Vec::new()).block_id(block_id).await?;
+    /// Creates the program input from the proof bundle.
+    fn create_input_from_bundle(
+        &self,
+        secret: WormholeSecret,
+        params: WormholeParams,
+        bundle: WormholeProofBundle,
+    ) -> anyhow::Result<WormholeProgramInputExt> {
+        let deposit_amount = bundle.deposit_proof.balance;
+        if let Some(warning) = max_deposit_warning(deposit_amount, params.max_deposit) {
+            eprintln!("WARNING: {warning}");
+        }
 
-        let cumulative_withdrawn_amount = self.cumulative_withdrawn_amount.unwrap_or_default();
-        if self.withdraw_amount.saturating_add(cumulative_withdrawn_amount) > deposit_proof.balance
+        let NullifierChainState { withdrawal_index, cumulative_withdrawn_amount } =
+            walk_nullifier_chain(
+                &secret,
+                &params,
+                deposit_amount,
+                self.cumulative_withdrawn_amount,
+                |slot| bundle.nullifier_storage(slot).map(|proof| proof.value),
+            )
+            .map_err(|error| match error {
+                NullifierChainError::MissingNullifier { nullifier, .. } => anyhow::anyhow!(
+                    "bundle is {error}, export the proofs with `--nullifier {nullifier}`"
+                ),
+                NullifierChainError::UnrecoverableAmount { .. } => {
+                    anyhow::anyhow!("{error}, provide it with `--cumulative-withdrawn-amount`")
+                }
+            })?;
+        if let Some(index) = self.withdrawal_index.filter(|index| *index != withdrawal_index) {
+            anyhow::bail!(
+                "withdrawal index {index} is inconsistent with the nullifier chain, \
+                 expected {withdrawal_index}"
+            );
+        }
+        if let Some(amount) =
+            self.cumulative_withdrawn_amount.filter(|amount| *amount != cumulative_withdrawn_amount)
         {
+            anyhow::bail!(
+                "cumulative withdrawn amount {amount} is inconsistent with the nullifier chain, \
+                 expected {cumulative_withdrawn_amount}"
+            );
+        }
+
+        let next_cumulative_withdrawn_amount =
+            self.withdraw_amount.saturating_add(cumulative_withdrawn_amount);
+        if next_cumulative_withdrawn_amount > deposit_amount {
             return Err(WormholeProgramError::InvalidWithdrawAmount.into());
         }
+        if next_cumulative_withdrawn_amount > params.max_withdrawable_amount(deposit_amount) {
+            return Err(WormholeProgramError::MaxDepositExceeded.into());
+        }
 
-        let mut nullifier_keys = Vec::new();
-        let withdrawal_index = self.withdrawal_index.unwrap_or_default();
-        if !withdrawal_index.is_zero() {
-            nullifier_keys.push(secret.nullifier(withdrawal_index - U256::from(1)));
+        let relayer_fee = self.relayer_fee.unwrap_or_default();
+        if relayer_fee > self.withdraw_amount {
+            return Err(WormholeProgramError::InvalidRelayerFee.into());
         }
-        let mut nullifier_proof =
-            provider.get_proof(self.nullifier_address, nullifier_keys).block_id(block_id).await?;
+
         let previous_nullifier_storage_proof = if withdrawal_index.is_zero() {
             Vec::new()
         } else {
-            // TODO: validate against `cumulative_withdrawn_amount`
-            nullifier_proof
-                .storage_proof
-                .pop()
+            let previous_nullifier =
+                secret.nullifier_with_params(&params, withdrawal_index - U256::from(1));
+            bundle
+                .nullifier_storage(previous_nullifier)
                 .ok_or(anyhow::anyhow!("missing previous nullifier proof"))?
                 .proof
+                .clone()
         };
 
-        let input = WormholeProgramInputExt {
+        Ok(WormholeProgramInputExt {
             inner: WormholeProgramInput {
                 secret,
-                deposit_amount: deposit_proof.balance,
+                deposit_amount,
                 withdraw_amount: self.withdraw_amount,
                 cumulative_withdrawn_amount,
                 withdrawal_index,
-                state_root: block.header.state_root,
-                deposit_account_proof: deposit_proof.account_proof,
+                state_root: bundle.header.state_root,
+                deposit_account_proof: bundle.deposit_proof.account_proof,
                 nullifier_address: self.nullifier_address,
-                nullifier_account_proof: nullifier_proof.account_proof,
+                nullifier_account_proof: bundle.nullifier_proof.account_proof,
                 previous_nullifier_storage_proof,
+                recipient: self.recipient,
+                relayer: self.relayer.unwrap_or_default(),
+                relayer_fee,
+                params,
             },
-            block_number: block.header.number,
-            block_hash: block.header.hash,
-        };
+            block_number: bundle.header.number,
+            block_hash: bundle.header.hash,
+            block_id: bundle.block_id,
+            confirmations: bundle.confirmations,
+        })
+    }
 
-        println!("{}", serde_json::to_string_pretty(&input)?);
+    /// Fetches the proof bundle with the window of nullifiers of the secret queried in a single
+    /// request. The window is doubled until the last nullifier in it is not stored in the
+    /// nullifier contract, so that the bundle covers the whole nullifier chain. Only the
+    /// nullifiers added to the window are queried on each doubling.
+    async fn fetch_bundle(
+        &self,
+        provider: &RootProvider,
+        block: &ResolvedBlock,
+        secret: &WormholeSecret,
+        params: &WormholeParams,
+        deposit_address: Address,
+    ) -> anyhow::Result<WormholeProofBundle> {
+        let nullifiers = |range: Range<u64>| {
+            Vec::from_iter(
+                range.map(|index| secret.nullifier_with_params(params, U256::from(index))),
+            )
+        };
+        let mut window = self.nullifier_window;
+        let mut bundle = WormholeProofBundle::fetch(
+            provider,
+            block,
+            &self.privacy,
+            self.nullifier_address,
+            deposit_address,
+            &nullifiers(0..window),
+        )
+        .await?;
+        loop {
+            let last = secret.nullifier_with_params(params, U256::from(window - 1));
+            if bundle.nullifier_storage(last).is_none_or(|proof| proof.value.is_zero()) {
+                return Ok(bundle);
+            }
 
-        Ok(())
+            eprintln!(
+                "WARNING: all {window} nullifiers in the window are spent, querying {window} more. \
+                 Increase `--nullifier-window` to avoid the repeated queries."
+            );
+            bundle
+                .fetch_more_nullifiers(
+                    provider,
+                    &self.privacy,
+                    self.nullifier_address,
+                    &nullifiers(window..window * 2),
+                )
+                .await?;
+            window *= 2;
+        }
     }
 }
 
+/// Returns the warning if the burn address balance exceeds the max deposit, in which case the
+/// excess can't be withdrawn.
+fn max_deposit_warning(deposit_amount: U256, max_deposit: U256) -> Option<String> {
+    (deposit_amount > max_deposit).then(|| {
+        format!(
+            "burn address balance {deposit_amount} exceeds the max deposit {max_deposit}. \
+             Only {max_deposit} can be withdrawn."
+        )
+    })
+}
+
 /// Wormhole program input extended with additional information.
 #[derive(Serialize, Deserialize, Debug)]
-struct WormholeProgramInputExt {
+pub struct WormholeProgramInputExt {
     /// Program input.
     #[serde(flatten)]
-    inner: WormholeProgramInput,
+    pub inner: WormholeProgramInput,
     /// The block number proofs were generated at.
-    block_number: u64,
+    pub block_number: u64,
     /// The block hash proofs were generated at.
-    block_hash: B256,
+    pub block_hash: B256,
+    /// The block requested with `--block` or selected with `--min-confirmations`.
+    #[serde(default)]
+    pub block_id: BlockId,
+    /// The number of blocks built on top of the block at the time the input was created.
+    #[serde(default)]
+    pub confirmations: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn max_deposit_warning_above_max_deposit() {
+        let max_deposit = U256::from(32);
+        assert_eq!(max_deposit_warning(U256::ZERO, max_deposit), None);
+        assert_eq!(max_deposit_warning(max_deposit, max_deposit), None);
+        assert_eq!(
+            max_deposit_warning(max_deposit + U256::from(1), max_deposit),
+            Some(
+                "burn address balance 33 exceeds the max deposit 32. Only 32 can be withdrawn."
+                    .into()
+            )
+        );
+    }
 }
@@ -0,0 +1,59 @@
+use crate::{keystore::KeystoreOutArgs, params::WormholeParamsArgs};
+use alloy_primitives::{hex, B256, U256};
+use alloy_wormhole::secret::WormholeMasterSeed;
+use clap::Parser;
+
+#[derive(Parser, Debug)]
+pub struct DeriveSecretCommand {
+    /// The BIP-39 mnemonic to derive the secrets from.
+    #[clap(long, env = "WORMHOLE_MNEMONIC", required_unless_present = "seed")]
+    mnemonic: Option<String>,
+
+    /// The BIP-39 passphrase.
+    #[clap(long, env = "WORMHOLE_PASSPHRASE", requires = "mnemonic")]
+    passphrase: Option<String>,
+
+    /// The 32-byte master seed to derive the secrets from.
+    #[clap(long, env = "WORMHOLE_MASTER_SEED", conflicts_with = "mnemonic")]
+    seed: Option<B256>,
+
+    /// The account index of the first secret.
+    #[clap(long, default_value_t = 0)]
+    index: u32,
+
+    /// The number of secrets to derive at consecutive account indices.
+    #[clap(long, default_value_t = 1, conflicts_with = "keystore")]
+    count: u32,
+
+    #[clap(flatten)]
+    params: WormholeParamsArgs,
+
+    #[clap(flatten)]
+    keystore: KeystoreOutArgs,
+}
+
+impl DeriveSecretCommand {
+    pub fn run(self) -> anyhow::Result<()> {
+        let seed = match (&self.mnemonic, self.seed) {
+            (Some(mnemonic), _) => WormholeMasterSeed::from_mnemonic(
+                mnemonic,
+                self.passphrase.as_deref().unwrap_or_default(),
+            )
+            .map_err(|error| anyhow::anyhow!("invalid mnemonic: {error}"))?,
+            (None, Some(seed)) => WormholeMasterSeed::new(seed),
+            (None, None) => anyhow::bail!("either mnemonic or seed must be provided"),
+        };
+
+        let params = self.params.params();
+        for index in self.index..self.index.saturating_add(self.count) {
+            let secret = seed.derive_secret_with_params(&params, index);
+            println!("Account: {index}");
+            if !self.keystore.write(&secret, params)? {
+                println!("Secret: {}", hex::encode(secret.as_ref()));
+            }
+            println!("Burn Address: {}", secret.burn_address_with_params(&params));
+            println!("Nullifier(0): {}", secret.nullifier_with_params(&params, U256::ZERO));
+        }
+        Ok(())
+    }
+}
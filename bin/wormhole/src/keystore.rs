@@ -0,0 +1,85 @@
+use alloy_primitives::Bytes;
+use alloy_wormhole::{WormholeKeystore, WormholeParams, WormholeSecret};
+use clap::Args;
+use std::{fs, path::PathBuf};
+use zeroize::Zeroizing;
+
+/// The source of the Wormhole secret.
+#[derive(Args, Clone, Debug)]
+pub struct SecretArgs {
+    /// The Wormhole secret.
+    #[clap(long, required_unless_present = "keystore", conflicts_with = "keystore")]
+    pub secret: Option<Bytes>,
+
+    /// The path to the encrypted secret keystore.
+    #[clap(long, requires = "password_file")]
+    pub keystore: Option<PathBuf>,
+
+    /// The path to the file containing the keystore password.
+    #[clap(long, requires = "keystore")]
+    pub password_file: Option<PathBuf>,
+}
+
+impl SecretArgs {
+    /// Returns the secret provided directly or decrypted from the keystore.
+    /// NOTE: The secret is not validated.
+    pub fn secret(&self) -> anyhow::Result<WormholeSecret> {
+        match (&self.secret, &self.keystore, &self.password_file) {
+            (Some(secret), _, _) => Ok(WormholeSecret::new_unchecked(secret.clone())),
+            (None, Some(keystore), Some(password_file)) => {
+                let keystore: WormholeKeystore = serde_json::from_slice(&fs::read(keystore)?)?;
+                Ok(keystore.decrypt(&read_password(password_file)?)?)
+            }
+            _ => anyhow::bail!("either secret or keystore with password file must be provided"),
+        }
+    }
+}
+
+/// The destination of the encrypted secret keystore.
+#[derive(Args, Clone, Debug)]
+pub struct KeystoreOutArgs {
+    /// The path to write the encrypted secret keystore to.
+    /// The secret is not printed if provided.
+    #[clap(long, requires = "password_file")]
+    pub keystore: Option<PathBuf>,
+
+    /// The path to the file containing the keystore password.
+    #[clap(long, requires = "keystore")]
+    pub password_file: Option<PathBuf>,
+
+    /// The optional keystore label.
+    #[clap(long, requires = "keystore")]
+    pub label: Option<String>,
+}
+
+impl KeystoreOutArgs {
+    /// Encrypts the secret and writes the keystore if the keystore path is provided.
+    /// Returns `true` if the keystore was written.
+    pub fn write(&self, secret: &WormholeSecret, params: WormholeParams) -> anyhow::Result<bool> {
+        let (Some(path), Some(password_file)) = (&self.keystore, &self.password_file) else {
+            return Ok(false);
+        };
+        if path.exists() {
+            anyhow::bail!("keystore {} already exists", path.display());
+        }
+
+        let mut keystore =
+            WormholeKeystore::encrypt(secret, params, &read_password(password_file)?)?;
+        if let Some(label) = &self.label {
+            keystore = keystore.with_label(label.clone());
+        }
+        fs::write(path, serde_json::to_string_pretty(&keystore)?)?;
+        println!("Keystore: {}", path.display());
+        Ok(true)
+    }
+}
+
+/// Reads the password from the file, trimming the trailing newline.
+/// The password is zeroized on drop.
+fn read_password(path: &PathBuf) -> anyhow::Result<Zeroizing<Vec<u8>>> {
+    let mut password = Zeroizing::new(fs::read(path)?);
+    while password.last().is_some_and(|byte| *byte == b'\n' || *byte == b'\r') {
+        password.pop();
+    }
+    Ok(password)
+}
@@ -1,7 +1,10 @@
-use alloy_primitives::{hex, U256};
-use alloy_wormhole::WormholeSecret;
 use clap::{Parser, Subcommand};
-use std::time::Instant;
+
+mod aggregate;
+use aggregate::AggregateCommand;
+
+mod batch_input;
+use batch_input::CreateBatchInputCommand;
 
 mod create_input;
 use create_input::CreateInputCommand;
@@ -13,6 +16,31 @@ use sp1::Sp1Command;
 mod risc0;
 use risc0::Risc0Command;
 
+mod export_proofs;
+use export_proofs::ExportProofsCommand;
+
+mod derive_secret;
+use derive_secret::DeriveSecretCommand;
+
+mod keystore;
+
+mod native;
+use native::NativeCommand;
+
+mod new_secret;
+use new_secret::NewSecretCommand;
+
+mod nullifiers;
+use nullifiers::NullifiersCommand;
+
+mod params;
+
+mod pico;
+use pico::PicoCommand;
+
+mod withdraw;
+use withdraw::WithdrawCommand;
+
 #[derive(Parser, Debug)]
 pub struct Cli {
     #[clap(subcommand)]
@@ -22,18 +50,18 @@ pub struct Cli {
 impl Cli {
     pub async fn run(self) -> anyhow::Result<()> {
         match self.command {
-            Command::NewSecret => {
-                let started_at = Instant::now();
-                let secret = WormholeSecret::random();
-                println!("Generated new secret in {:?}", started_at.elapsed());
-                println!("Secret: {}", hex::encode(secret.as_ref()));
-                println!("Burn Address: {}", secret.burn_address());
-                println!("Nullifier(0): {}", secret.nullifier(U256::ZERO));
-                Ok(())
-            }
+            Command::NewSecret(cmd) => cmd.run(),
+            Command::DeriveSecret(cmd) => cmd.run(),
+            Command::Nullifiers(cmd) => cmd.run(),
             Command::CreateInput(cmd) => cmd.run().await,
+            Command::ExportProofs(cmd) => cmd.run().await,
+            Command::CreateBatchInput(cmd) => cmd.run(),
             Command::Sp1(cmd) => cmd.run(),
             Command::Risc0(cmd) => cmd.run(),
+            Command::Pico(cmd) => cmd.run(),
+            Command::Native(cmd) => cmd.run(),
+            Command::Withdraw(cmd) => cmd.run().await,
+            Command::Aggregate(cmd) => cmd.run(),
         }
     }
 }
@@ -41,11 +69,27 @@ impl Cli {
 #[derive(Subcommand, Debug)]
 pub enum Command {
     #[command(name = "new-secret")]
-    NewSecret,
+    NewSecret(NewSecretCommand),
+    #[command(name = "derive-secret")]
+    DeriveSecret(DeriveSecretCommand),
+    #[command(name = "nullifiers")]
+    Nullifiers(NullifiersCommand),
     #[command(name = "create-input")]
     CreateInput(CreateInputCommand),
+    #[command(name = "export-proofs")]
+    ExportProofs(ExportProofsCommand),
+    #[command(name = "create-batch-input")]
+    CreateBatchInput(CreateBatchInputCommand),
     #[command(name = "sp1")]
     Sp1(Sp1Command),
     #[command(name = "risc0")]
     Risc0(Risc0Command),
+    #[command(name = "pico")]
+    Pico(PicoCommand),
+    #[command(name = "native")]
+    Native(NativeCommand),
+    #[command(name = "withdraw")]
+    Withdraw(WithdrawCommand),
+    #[command(name = "aggregate")]
+    Aggregate(AggregateCommand),
 }
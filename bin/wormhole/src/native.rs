@@ -0,0 +1,80 @@
+use crate::batch_input::read_program_input;
+use clap::{Parser, Subcommand};
+use std::{
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+use wormhole_program_core::{
+    execute_wormhole_batch_program_with_observer, WormholeProgramObserver, WormholeProgramStep,
+};
+
+#[derive(Parser, Debug)]
+pub struct NativeCommand {
+    #[clap(subcommand)]
+    subcommand: NativeSubcommand,
+
+    #[clap(long)]
+    input: PathBuf,
+}
+
+impl NativeCommand {
+    pub fn run(self) -> anyhow::Result<()> {
+        let input = read_program_input(&self.input)?;
+
+        match self.subcommand {
+            NativeSubcommand::Execute => {
+                let mut timer = StepTimer::default();
+                let started_at = Instant::now();
+                let result = execute_wormhole_batch_program_with_observer(input, &mut timer);
+                let elapsed = started_at.elapsed();
+
+                for (step, elapsed) in &timer.completed {
+                    println!("{step}: {elapsed:?}");
+                }
+
+                match result {
+                    Ok(output) => {
+                        println!("Output: {output:?}");
+                        println!("Executed in {elapsed:?}");
+                    }
+                    Err(error) => {
+                        if let Some((step, _)) = timer.current {
+                            println!("{step}: failed");
+                        }
+                        return Err(error.into());
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Records the execution time of each program step.
+#[derive(Default, Debug)]
+struct StepTimer {
+    /// The step currently being executed.
+    current: Option<(WormholeProgramStep, Instant)>,
+    /// The completed steps with their execution time.
+    completed: Vec<(WormholeProgramStep, Duration)>,
+}
+
+impl WormholeProgramObserver for StepTimer {
+    fn on_step_start(&mut self, step: WormholeProgramStep) {
+        self.current = Some((step, Instant::now()));
+    }
+
+    fn on_step_end(&mut self, step: WormholeProgramStep) {
+        if let Some((_, started_at)) = self.current.take() {
+            self.completed.push((step, started_at.elapsed()));
+        }
+    }
+}
+
+#[derive(Subcommand, Debug)]
+pub enum NativeSubcommand {
+    /// Execute the program on the host without a zkVM.
+    #[command(name = "execute")]
+    Execute,
+}
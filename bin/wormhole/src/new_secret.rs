@@ -0,0 +1,53 @@
+use crate::{keystore::KeystoreOutArgs, params::WormholeParamsArgs};
+use alloy_primitives::{hex, U256};
+use alloy_wormhole::secret::WormholeSecretMiner;
+use clap::Parser;
+use std::{io::Write, num::NonZeroUsize, time::Instant};
+
+#[derive(Parser, Debug)]
+pub struct NewSecretCommand {
+    /// The number of mining threads. Defaults to the number of available threads.
+    #[clap(long)]
+    threads: Option<NonZeroUsize>,
+
+    #[clap(flatten)]
+    params: WormholeParamsArgs,
+
+    #[clap(flatten)]
+    keystore: KeystoreOutArgs,
+}
+
+impl NewSecretCommand {
+    pub fn run(self) -> anyhow::Result<()> {
+        let params = self.params.params();
+        let mut miner = WormholeSecretMiner::new(params);
+        if let Some(threads) = self.threads {
+            miner = miner.with_threads(threads);
+        }
+
+        let started_at = Instant::now();
+        let secret = miner
+            .mine(|progress| {
+                let eta = progress
+                    .eta()
+                    .map_or_else(|| "unknown".to_string(), |eta| format!("{eta:.0?}"));
+                eprint!(
+                    "\rMining: {} attempts, {:.2} MH/s, ETA ~{eta}    ",
+                    progress.attempts,
+                    progress.hash_rate() / 1e6,
+                );
+                let _ = std::io::stderr().flush();
+            })
+            .map_err(|error| anyhow::anyhow!("failed to generate secret: {error}"))?
+            .ok_or_else(|| anyhow::anyhow!("secret mining cancelled"))?;
+        eprintln!();
+
+        println!("Generated new secret in {:?}", started_at.elapsed());
+        if !self.keystore.write(&secret, params)? {
+            println!("Secret: {}", hex::encode(secret.as_ref()));
+        }
+        println!("Burn Address: {}", secret.burn_address_with_params(&params));
+        println!("Nullifier(0): {}", secret.nullifier_with_params(&params, U256::ZERO));
+        Ok(())
+    }
+}
@@ -0,0 +1,46 @@
+use crate::{keystore::SecretArgs, params::WormholeParamsArgs};
+use alloy_primitives::U256;
+use clap::Parser;
+use wormhole_program_core::WormholeProgramError;
+
+#[derive(Parser, Debug)]
+pub struct NullifiersCommand {
+    #[clap(flatten)]
+    secret: SecretArgs,
+
+    /// The number of nullifiers to print, starting from the first withdrawal. Should exceed the
+    /// number of withdrawals from the burn address, so that a single `export-proofs` covers the
+    /// whole nullifier chain.
+    #[clap(long, default_value_t = 1)]
+    count: u64,
+
+    #[clap(flatten)]
+    params: WormholeParamsArgs,
+}
+
+impl NullifiersCommand {
+    pub fn run(self) -> anyhow::Result<()> {
+        let params = self.params.params();
+        let secret = self.secret.secret()?;
+        if !secret.has_valid_length() {
+            return Err(WormholeProgramError::InvalidSecretLength.into());
+        }
+        if !secret.is_valid_with_params(&params) {
+            return Err(WormholeProgramError::InvalidSecret.into());
+        }
+
+        let address = secret.burn_address_with_params(&params);
+        let nullifiers = Vec::from_iter(
+            (0..self.count).map(|index| secret.nullifier_with_params(&params, U256::from(index))),
+        );
+        println!("Burn Address: {address}");
+        for (index, nullifier) in nullifiers.iter().enumerate() {
+            println!("Nullifier({index}): {nullifier}");
+        }
+
+        // The arguments for `export-proofs` on the online machine.
+        let args = nullifiers.iter().map(|nullifier| format!(" --nullifier {nullifier}"));
+        println!("Export Args: --address {address}{}", String::from_iter(args));
+        Ok(())
+    }
+}
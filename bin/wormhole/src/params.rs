@@ -0,0 +1,24 @@
+use alloy_primitives::U256;
+use alloy_wormhole::{WormholeParams, MAX_DEPOSIT, POW_LOG_DIFFICULTY};
+use clap::Args;
+
+/// The Wormhole protocol parameters.
+#[derive(Args, Clone, Debug)]
+pub struct WormholeParamsArgs {
+    /// The exponent for Proof-of-Work condition on the secret.
+    #[clap(long, default_value_t = POW_LOG_DIFFICULTY)]
+    pub pow_log_difficulty: u8,
+
+    /// The maximum allowed value of the Wormhole deposit.
+    #[clap(long, default_value_t = MAX_DEPOSIT)]
+    pub max_deposit: U256,
+}
+
+impl WormholeParamsArgs {
+    /// Returns the Wormhole parameters.
+    pub fn params(&self) -> WormholeParams {
+        WormholeParams::DEFAULT
+            .with_pow_log_difficulty(self.pow_log_difficulty)
+            .with_max_deposit(self.max_deposit)
+    }
+}
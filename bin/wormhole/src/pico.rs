@@ -0,0 +1,88 @@
+use crate::batch_input::read_program_input;
+use anyhow::Context;
+use clap::{Parser, Subcommand};
+use pico_sdk::{client::DefaultProverClient, init_logger};
+use std::{fs, path::PathBuf};
+use wormhole_program_core::WormholeProgramOutput;
+
+/// The ELF (executable and linkable format) file for the Pico RISC-V zkVM.
+/// Empty if the program build is skipped with `PICO_SKIP_PROGRAM_BUILD`.
+pub const WORMHOLE_PROGRAM_PICO_ELF: &[u8] =
+    include_bytes!(concat!(env!("OUT_DIR"), "/riscv32im-pico-zkvm-elf"));
+
+#[derive(Parser, Debug)]
+pub struct PicoCommand {
+    #[clap(subcommand)]
+    subcommand: PicoSubcommand,
+
+    #[clap(long)]
+    input: PathBuf,
+}
+
+impl PicoCommand {
+    pub fn run(self) -> anyhow::Result<()> {
+        // Setup the logger.
+        init_logger();
+
+        if WORMHOLE_PROGRAM_PICO_ELF.is_empty() {
+            anyhow::bail!(
+                "Pico program is not built, unset PICO_SKIP_PROGRAM_BUILD and rebuild with `cargo pico`"
+            );
+        }
+
+        // Setup the prover client.
+        let client = DefaultProverClient::new(WORMHOLE_PROGRAM_PICO_ELF);
+
+        // Setup the inputs.
+        let input = read_program_input(&self.input)?;
+        let mut stdin_builder = client.new_stdin_builder();
+        stdin_builder.write(&input);
+
+        match self.subcommand {
+            PicoSubcommand::Execute => {
+                let (cycles, public_values) = client.emulate(stdin_builder);
+
+                // Print the output.
+                let output = WormholeProgramOutput::from_public_values(&public_values)?;
+                println!("Output: {output:?}");
+
+                // Record the number of cycles executed.
+                println!("Number of cycles: {cycles}");
+            }
+            PicoSubcommand::Prove { verify, out } => {
+                // The Pico prover client does not expose the proof verification, fail before
+                // spending the time on proving.
+                anyhow::ensure!(!verify, "Pico proof verification is not supported");
+
+                // Generate the proof.
+                let proof = client.prove_fast(stdin_builder).context("proof generation failed")?;
+
+                let public_values = proof.pv_stream.clone().context("missing public values")?;
+                let output = WormholeProgramOutput::from_public_values(&public_values)?;
+                println!("Output: {output:?}");
+
+                if let Some(out) = out {
+                    fs::write(out, bincode::serialize(&proof)?)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Subcommand, Debug)]
+pub enum PicoSubcommand {
+    #[command(name = "execute")]
+    Execute,
+    #[command(name = "prove")]
+    Prove {
+        /// Flag indicating whether we should verify the proof. Not supported by Pico yet.
+        #[clap(long)]
+        verify: bool,
+
+        /// The optional path to write the bincode serialized proof to.
+        #[clap(long)]
+        out: Option<PathBuf>,
+    },
+}
@@ -1,7 +1,12 @@
+use crate::{aggregate::WormholeAggregationProof, batch_input::read_program_input};
+use alloy_primitives::B256;
+use alloy_wormhole::{WormholeProofSystem, WormholeTxProof};
 use clap::{Parser, Subcommand};
-use risc0_zkvm::{default_executor, default_prover, ExecutorEnv, ProverOpts};
+use risc0_zkvm::{default_executor, default_prover, sha::Digest, ExecutorEnv, ProverOpts};
 use std::{fs, path::PathBuf};
-use wormhole_program_core::{WormholeProgramInput, WormholeProgramOutput};
+use wormhole_program_core::{
+    WormholeAggregationOutput, WormholeBatchProgramInput, WormholeProgramOutput,
+};
 
 include!(concat!(env!("OUT_DIR"), "/methods.rs"));
 
@@ -16,7 +21,7 @@ pub struct Risc0Command {
 
 impl Risc0Command {
     pub fn run(self) -> anyhow::Result<()> {
-        let input: WormholeProgramInput = serde_json::from_slice(&fs::read(&self.input)?)?;
+        let input = read_program_input(&self.input)?;
         let env = ExecutorEnv::builder()
             // Send input to the guest
             .write(&input)?
@@ -28,13 +33,13 @@ impl Risc0Command {
                 let session = executor.execute(env, WORMHOLE_PROGRAM_RISC0_ELF)?;
 
                 // Print the output.
-                let output: WormholeProgramOutput = session.journal.decode()?;
+                let output = WormholeProgramOutput::from_public_values(&session.journal.bytes)?;
                 println!("Output: {output:?}");
 
                 // Record the number of cycles executed.
                 println!("Number of cycles: {}", session.cycles());
             }
-            Risc0Subcommand::Prove { verify } => {
+            Risc0Subcommand::Prove { verify, out } => {
                 // Obtain the default prover.
                 let prover = default_prover();
 
@@ -46,9 +51,14 @@ impl Risc0Command {
                 )?;
                 println!("Receipt: {:?}", info.receipt);
 
-                let output: WormholeProgramOutput = info.receipt.journal.decode()?;
+                let output =
+                    WormholeProgramOutput::from_public_values(&info.receipt.journal.bytes)?;
                 println!("Output: {output:?}");
 
+                if let Some(out) = out {
+                    fs::write(out, bincode::serialize(&info.receipt)?)?;
+                }
+
                 if verify {
                     info.receipt.verify(WORMHOLE_PROGRAM_RISC0_ID)?;
                 }
@@ -59,6 +69,65 @@ impl Risc0Command {
     }
 }
 
+/// Generates and verifies the Groth16 receipt of the program execution.
+pub fn prove_wormhole_tx_proof(
+    input: &WormholeBatchProgramInput,
+) -> anyhow::Result<WormholeTxProof> {
+    let env = ExecutorEnv::builder().write(input)?.build()?;
+
+    let info = default_prover().prove_with_opts(
+        env,
+        WORMHOLE_PROGRAM_RISC0_ELF,
+        &ProverOpts::groth16(),
+    )?;
+    info.receipt.verify(WORMHOLE_PROGRAM_RISC0_ID)?;
+
+    let output = WormholeProgramOutput::from_public_values(&info.receipt.journal.bytes)?;
+    Ok(WormholeTxProof::new(
+        output,
+        WormholeProofSystem::RISC0_V2,
+        B256::from_slice(Digest::from(WORMHOLE_PROGRAM_RISC0_ID).as_bytes()),
+        bincode::serialize(&info.receipt)?.into(),
+    ))
+}
+
+/// Generates the succinct receipts of the program execution for each input and aggregates them
+/// into a single Groth16 receipt using composition.
+pub fn aggregate_wormhole_proofs(
+    inputs: &[WormholeBatchProgramInput],
+) -> anyhow::Result<WormholeAggregationProof> {
+    let prover = default_prover();
+
+    let mut env = ExecutorEnv::builder();
+    let mut journals = Vec::with_capacity(inputs.len());
+    for input in inputs {
+        let inner_env = ExecutorEnv::builder().write(input)?.build()?;
+        let receipt = prover
+            .prove_with_opts(inner_env, WORMHOLE_PROGRAM_RISC0_ELF, &ProverOpts::succinct())?
+            .receipt;
+        journals.push(receipt.journal.bytes.clone());
+        env.add_assumption(receipt);
+    }
+    let env = env.write(&WORMHOLE_PROGRAM_RISC0_ID)?.write(&journals)?.build()?;
+
+    let info =
+        prover.prove_with_opts(env, WORMHOLE_AGGREGATOR_RISC0_ELF, &ProverOpts::groth16())?;
+    info.receipt.verify(WORMHOLE_AGGREGATOR_RISC0_ID)?;
+
+    let output = WormholeAggregationOutput::from_public_values(&info.receipt.journal.bytes)?;
+    anyhow::ensure!(
+        output.program_id ==
+            WormholeAggregationOutput::program_id_from_words(WORMHOLE_PROGRAM_RISC0_ID),
+        "aggregated program mismatch"
+    );
+    Ok(WormholeAggregationProof {
+        proof_system: WormholeProofSystem::RISC0_V2,
+        program_id: B256::from_slice(Digest::from(WORMHOLE_AGGREGATOR_RISC0_ID).as_bytes()),
+        output,
+        proof: bincode::serialize(&info.receipt)?.into(),
+    })
+}
+
 #[derive(Subcommand, Debug)]
 pub enum Risc0Subcommand {
     #[command(name = "execute")]
@@ -68,5 +137,9 @@ pub enum Risc0Subcommand {
         /// Flag indicating whether we should verify the proof.
         #[clap(long)]
         verify: bool,
+
+        /// The optional path to write the bincode serialized receipt to.
+        #[clap(long)]
+        out: Option<PathBuf>,
     },
 }
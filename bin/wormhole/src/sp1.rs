@@ -1,12 +1,20 @@
+use crate::{aggregate::WormholeAggregationProof, batch_input::read_program_input};
+use alloy_primitives::B256;
+use alloy_wormhole::{WormholeProofSystem, WormholeTxProof};
 use anyhow::Context;
 use clap::{Parser, Subcommand};
-use sp1_sdk::{include_elf, ProverClient, SP1Stdin};
+use sp1_sdk::{include_elf, HashableKey, ProverClient, SP1Proof, SP1Stdin};
 use std::{fs, path::PathBuf};
-use wormhole_program_core::WormholeProgramInput;
+use wormhole_program_core::{
+    WormholeAggregationOutput, WormholeBatchProgramInput, WormholeProgramOutput,
+};
 
 /// The ELF (executable and linkable format) file for the Succinct RISC-V zkVM.
 pub const WORMHOLE_PROGRAM_SP1_ELF: &[u8] = include_elf!("wormhole-program-sp1");
 
+/// The ELF file of the aggregation program for the Succinct RISC-V zkVM.
+pub const WORMHOLE_AGGREGATOR_SP1_ELF: &[u8] = include_elf!("wormhole-aggregator-sp1");
+
 #[derive(Parser, Debug)]
 pub struct Sp1Command {
     #[clap(subcommand)]
@@ -25,7 +33,7 @@ impl Sp1Command {
         let client = ProverClient::from_env();
 
         // Setup the inputs.
-        let input: WormholeProgramInput = serde_json::from_slice(&fs::read(&self.input)?)?;
+        let input = read_program_input(&self.input)?;
         let mut stdin = SP1Stdin::new();
         stdin.write(&input);
 
@@ -37,6 +45,7 @@ impl Sp1Command {
                     .context("program execution failed")?;
 
                 // Print the output.
+                let output = WormholeProgramOutput::from_public_values(output.as_slice())?;
                 println!("Output: {output:?}");
 
                 // Record the number of cycles executed.
@@ -68,6 +77,76 @@ impl Sp1Command {
     }
 }
 
+/// Generates and verifies the Groth16 proof of the program execution.
+pub fn prove_wormhole_tx_proof(
+    input: &WormholeBatchProgramInput,
+) -> anyhow::Result<WormholeTxProof> {
+    let client = ProverClient::from_env();
+
+    let mut stdin = SP1Stdin::new();
+    stdin.write(input);
+
+    let (pk, vk) = client.setup(WORMHOLE_PROGRAM_SP1_ELF);
+    let proof = client.prove(&pk, &stdin).groth16().run().context("proof generation failed")?;
+    client.verify(&proof, &vk).context("proof verification failed")?;
+
+    let output = WormholeProgramOutput::from_public_values(proof.public_values.as_slice())?;
+    Ok(WormholeTxProof::new(
+        output,
+        WormholeProofSystem::SP1_V4_GROTH16,
+        B256::from(vk.bytes32_raw()),
+        proof.bytes().into(),
+    ))
+}
+
+/// Generates the compressed proofs of the program execution for each input and aggregates them
+/// into a single Groth16 proof.
+pub fn aggregate_wormhole_proofs(
+    inputs: &[WormholeBatchProgramInput],
+) -> anyhow::Result<WormholeAggregationProof> {
+    let client = ProverClient::from_env();
+    let (pk, vk) = client.setup(WORMHOLE_PROGRAM_SP1_ELF);
+
+    let mut proofs = Vec::with_capacity(inputs.len());
+    for input in inputs {
+        let mut stdin = SP1Stdin::new();
+        stdin.write(input);
+        let proof =
+            client.prove(&pk, &stdin).compressed().run().context("proof generation failed")?;
+        proofs.push(proof);
+    }
+
+    let mut stdin = SP1Stdin::new();
+    stdin.write(&vk.hash_u32());
+    stdin.write(&proofs.iter().map(|proof| proof.public_values.to_vec()).collect::<Vec<_>>());
+    for proof in proofs {
+        let SP1Proof::Compressed(proof) = proof.proof else {
+            anyhow::bail!("expected compressed proof");
+        };
+        stdin.write_proof(*proof, vk.vk.clone());
+    }
+
+    let (aggregator_pk, aggregator_vk) = client.setup(WORMHOLE_AGGREGATOR_SP1_ELF);
+    let proof = client
+        .prove(&aggregator_pk, &stdin)
+        .groth16()
+        .run()
+        .context("aggregation proof generation failed")?;
+    client.verify(&proof, &aggregator_vk).context("aggregation proof verification failed")?;
+
+    let output = WormholeAggregationOutput::from_public_values(proof.public_values.as_slice())?;
+    anyhow::ensure!(
+        output.program_id == WormholeAggregationOutput::program_id_from_words(vk.hash_u32()),
+        "aggregated program mismatch"
+    );
+    Ok(WormholeAggregationProof {
+        proof_system: WormholeProofSystem::SP1_V4_GROTH16,
+        program_id: B256::from(aggregator_vk.bytes32_raw()),
+        output,
+        proof: proof.bytes().into(),
+    })
+}
+
 #[derive(Subcommand, Debug)]
 pub enum Sp1Subcommand {
     #[command(name = "execute")]
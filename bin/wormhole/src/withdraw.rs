@@ -0,0 +1,107 @@
+use crate::{create_input::CreateInputCommand, risc0, sp1};
+use alloy_consensus::{transaction::RlpEcdsaEncodableTx, SignableTransaction};
+use alloy_eip2930::AccessList;
+use alloy_primitives::{hex, Bytes};
+use alloy_provider::Provider;
+use alloy_signer::SignerSync;
+use alloy_signer_local::PrivateKeySigner;
+use alloy_wormhole::WormholeTx;
+use clap::{Parser, ValueEnum};
+use wormhole_program_core::WormholeBatchProgramInput;
+
+#[derive(Parser, Debug)]
+pub struct WithdrawCommand {
+    #[clap(flatten)]
+    input: CreateInputCommand,
+
+    /// The zkVM backend to generate the proof with.
+    #[clap(long, value_enum, default_value_t = Backend::Sp1)]
+    backend: Backend,
+
+    /// The private key of the transaction signer.
+    #[clap(long, env = "WORMHOLE_PRIVATE_KEY")]
+    private_key: PrivateKeySigner,
+
+    /// The transaction gas limit.
+    #[clap(long, default_value_t = 500_000)]
+    gas_limit: u64,
+
+    /// The max fee per gas. Estimated if not provided.
+    #[clap(long, requires = "max_priority_fee_per_gas")]
+    max_fee_per_gas: Option<u128>,
+
+    /// The max priority fee per gas. Estimated if not provided.
+    #[clap(long, requires = "max_fee_per_gas")]
+    max_priority_fee_per_gas: Option<u128>,
+
+    /// Flag indicating whether the signed transaction should be broadcast.
+    /// Otherwise, the raw EIP-2718 transaction envelope is printed.
+    #[clap(long)]
+    broadcast: bool,
+}
+
+impl WithdrawCommand {
+    pub async fn run(self) -> anyhow::Result<()> {
+        let rpc_url =
+            self.input.rpc_url.as_deref().ok_or(anyhow::anyhow!("RPC URL must be provided"))?;
+        let input = self.input.create_input().await?;
+        println!("Created program input at block {} ({})", input.block_number, input.block_hash);
+
+        let batch_input = WormholeBatchProgramInput::from(input.inner.clone());
+        let proof = match self.backend {
+            Backend::Sp1 => sp1::prove_wormhole_tx_proof(&batch_input)?,
+            Backend::Risc0 => risc0::prove_wormhole_tx_proof(&batch_input)?,
+        };
+        for entry in &proof.nullifiers {
+            println!("Generated {} proof for nullifier {}", proof.proof_system, entry.nullifier);
+        }
+
+        let provider = self.input.privacy.connect(rpc_url).await?;
+        let chain_id = provider.get_chain_id().await?;
+        let nonce = provider.get_transaction_count(self.private_key.address()).await?;
+        let (max_fee_per_gas, max_priority_fee_per_gas) =
+            match (self.max_fee_per_gas, self.max_priority_fee_per_gas) {
+                (Some(max_fee_per_gas), Some(max_priority_fee_per_gas)) => {
+                    (max_fee_per_gas, max_priority_fee_per_gas)
+                }
+                _ => {
+                    let estimate = provider.estimate_eip1559_fees().await?;
+                    (estimate.max_fee_per_gas, estimate.max_priority_fee_per_gas)
+                }
+            };
+
+        let tx = WormholeTx {
+            chain_id,
+            nonce,
+            gas_limit: self.gas_limit,
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+            to: input.inner.recipient,
+            input: Bytes::new(),
+            access_list: AccessList::default(),
+            proof_block_number: input.block_number,
+            proof,
+        };
+        tx.validate_recipient()?;
+
+        let signature = self.private_key.sign_hash_sync(&tx.signature_hash())?;
+        let mut encoded = Vec::new();
+        tx.eip2718_encode(&signature, &mut encoded);
+
+        if self.broadcast {
+            let pending = provider.send_raw_transaction(&encoded).await?;
+            println!("Transaction hash: {}", pending.tx_hash());
+        } else {
+            println!("Transaction: {}", hex::encode_prefixed(&encoded));
+        }
+
+        Ok(())
+    }
+}
+
+/// The zkVM backend.
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum Backend {
+    Sp1,
+    Risc0,
+}
@@ -8,10 +8,12 @@ contract MockNullifierSystemContract {
         ward = msg.sender;
     }
 
-    function nullify(bytes32 nullifier, bytes32 hash) public {
+    function nullify(bytes32 nullifier, bytes32 hash, bytes32 encryptedAmount) public {
         require(msg.sender == ward);
+        bytes32 encryptedAmountSlot = keccak256(abi.encode(nullifier));
         assembly {
             sstore(nullifier, hash)
+            sstore(encryptedAmountSlot, encryptedAmount)
         }
     }
 }
@@ -6,6 +6,27 @@ pub const MAGIC_ADDRESS: u8 = 0xfe;
 /// The salt byte for computing for nullifier.
 pub const MAGIC_NULLIFIER: u8 = 0x01;
 
+/// The domain separation tag for computing the nullifier.
+pub const NULLIFIER_DOMAIN: &[u8] = b"wormhole-nullifier";
+
+/// The version of the nullifier derivation scheme.
+pub const NULLIFIER_VERSION: u8 = 0x01;
+
+/// The domain separation tag for deriving the cumulative withdrawn amount encryption key.
+pub const AMOUNT_ENCRYPTION_DOMAIN: &[u8] = b"wormhole-amount";
+
+/// The domain separation tag for deriving the master seed from the BIP-39 seed.
+pub const HD_MASTER_DOMAIN: &[u8] = b"wormhole-hd-master";
+
+/// The domain separation tag for deriving the secret candidates from the master seed.
+pub const HD_SECRET_DOMAIN: &[u8] = b"wormhole-hd-secret";
+
+/// The length of the secret in bytes.
+///
+/// The secret must carry at least 128 bits of entropy, otherwise the burn addresses could be
+/// brute-forced. The fixed 32-byte length leaves a margin on top of the Proof-of-Work condition.
+pub const SECRET_LENGTH: usize = 32;
+
 /// The salt for Proof-of-Work condition on the secret.
 pub const MAGIC_POW: u8 = 0x02;
 
@@ -0,0 +1,26 @@
+use alloy_primitives::Address;
+use core::fmt;
+
+/// The error returned by [`WormholeTx`](crate::WormholeTx) validation.
+#[derive(PartialEq, Eq, Debug)]
+pub enum WormholeTxError {
+    /// The recipient committed to by the proof does not match the transaction beneficiary.
+    RecipientMismatch {
+        /// The transaction beneficiary.
+        expected: Address,
+        /// The recipient committed to by the proof.
+        got: Address,
+    },
+}
+
+impl core::error::Error for WormholeTxError {}
+
+impl fmt::Display for WormholeTxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RecipientMismatch { expected, got } => {
+                write!(f, "recipient mismatch: expected {expected}, got {got}")
+            }
+        }
+    }
+}
@@ -0,0 +1,308 @@
+//! Password-encrypted [`WormholeSecret`] keystore.
+//!
+//! The keystore format is modelled after the Ethereum keystore v3. The secret is encrypted with
+//! AES-256-GCM using the key derived from the password with scrypt. The burn address and the
+//! protocol parameters are stored in plaintext and authenticated as the associated data.
+
+use crate::{WormholeParams, WormholeSecret};
+use aes_gcm::{
+    aead::{Aead, KeyInit, Payload},
+    Aes256Gcm, Nonce,
+};
+use alloc::{string::String, vec::Vec};
+use alloy_primitives::{Address, Bytes, B256};
+use core::fmt;
+use zeroize::Zeroizing;
+
+/// The current keystore version.
+pub const KEYSTORE_VERSION: u8 = 1;
+
+/// The name of the keystore cipher.
+pub const KEYSTORE_CIPHER: &str = "aes-256-gcm";
+
+/// The name of the keystore key derivation function.
+pub const KEYSTORE_KDF: &str = "scrypt";
+
+/// The password-encrypted [`WormholeSecret`] keystore.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct WormholeKeystore {
+    /// The keystore version.
+    pub version: u8,
+    /// The burn address of the encrypted secret.
+    pub address: Address,
+    /// The protocol parameters the secret is valid under.
+    pub params: WormholeParams,
+    /// The optional user-defined label.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// The encrypted secret.
+    pub crypto: KeystoreCrypto,
+}
+
+/// The encrypted secret and the parameters for its decryption.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct KeystoreCrypto {
+    /// The cipher name.
+    pub cipher: String,
+    /// The encrypted secret including the authentication tag.
+    pub ciphertext: Bytes,
+    /// The cipher parameters.
+    pub cipherparams: KeystoreCipherParams,
+    /// The key derivation function name.
+    pub kdf: String,
+    /// The key derivation function parameters.
+    pub kdfparams: KeystoreKdfParams,
+}
+
+/// The cipher parameters.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct KeystoreCipherParams {
+    /// The 12-byte AES-GCM nonce.
+    pub nonce: Bytes,
+}
+
+/// The scrypt key derivation function parameters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct KeystoreKdfParams {
+    /// The derived key length.
+    pub dklen: u32,
+    /// The CPU/memory cost parameter. Must be a power of two.
+    pub n: u64,
+    /// The block size parameter.
+    pub r: u32,
+    /// The parallelization parameter.
+    pub p: u32,
+    /// The salt.
+    pub salt: B256,
+}
+
+impl KeystoreKdfParams {
+    /// The recommended scrypt parameters, same as the Ethereum keystore v3 defaults.
+    pub const fn recommended(salt: B256) -> Self {
+        Self { dklen: 32, n: 1 << 18, r: 8, p: 1, salt }
+    }
+
+    fn derive_key(&self, password: &[u8]) -> Result<Zeroizing<[u8; 32]>, WormholeKeystoreError> {
+        if self.dklen != 32 || !self.n.is_power_of_two() {
+            return Err(WormholeKeystoreError::InvalidKdfParams);
+        }
+        let log_n = self.n.trailing_zeros() as u8;
+        let params = scrypt::Params::new(log_n, self.r, self.p, 32)
+            .map_err(|_| WormholeKeystoreError::InvalidKdfParams)?;
+        let mut key = Zeroizing::new([0u8; 32]);
+        scrypt::scrypt(password, self.salt.as_slice(), &params, key.as_mut_slice())
+            .map_err(|_| WormholeKeystoreError::InvalidKdfParams)?;
+        Ok(key)
+    }
+}
+
+impl WormholeKeystore {
+    /// Encrypts the secret with the password using the recommended scrypt parameters and random
+    /// salt and nonce.
+    pub fn encrypt(
+        secret: &WormholeSecret,
+        params: WormholeParams,
+        password: &[u8],
+    ) -> Result<Self, WormholeKeystoreError> {
+        let mut salt = B256::ZERO;
+        getrandom::getrandom(salt.as_mut_slice())?;
+        let mut nonce = [0u8; 12];
+        getrandom::getrandom(&mut nonce)?;
+        Self::encrypt_with(secret, params, password, KeystoreKdfParams::recommended(salt), nonce)
+    }
+
+    /// Encrypts the secret with the password using provided scrypt parameters and nonce.
+    ///
+    /// NOTE: The nonce must never be reused with the same key.
+    pub fn encrypt_with(
+        secret: &WormholeSecret,
+        params: WormholeParams,
+        password: &[u8],
+        kdfparams: KeystoreKdfParams,
+        nonce: [u8; 12],
+    ) -> Result<Self, WormholeKeystoreError> {
+        let address = secret.burn_address_with_params(&params);
+        let key = kdfparams.derive_key(password)?;
+        let aad = associated_data(address, &params);
+        let plaintext: &Bytes = secret.as_ref();
+        let ciphertext = Aes256Gcm::new((&*key).into())
+            .encrypt(Nonce::from_slice(&nonce), Payload { msg: plaintext, aad: &aad })
+            .map_err(|_| WormholeKeystoreError::Encryption)?;
+        Ok(Self {
+            version: KEYSTORE_VERSION,
+            address,
+            params,
+            label: None,
+            crypto: KeystoreCrypto {
+                cipher: KEYSTORE_CIPHER.into(),
+                ciphertext: ciphertext.into(),
+                cipherparams: KeystoreCipherParams { nonce: nonce.to_vec().into() },
+                kdf: KEYSTORE_KDF.into(),
+                kdfparams,
+            },
+        })
+    }
+
+    /// Sets the user-defined label.
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Decrypts the secret with the password.
+    ///
+    /// Verifies that the decrypted secret is valid under the keystore parameters and matches
+    /// the keystore burn address.
+    pub fn decrypt(&self, password: &[u8]) -> Result<WormholeSecret, WormholeKeystoreError> {
+        if self.version != KEYSTORE_VERSION {
+            return Err(WormholeKeystoreError::UnsupportedVersion(self.version));
+        }
+        if self.crypto.cipher != KEYSTORE_CIPHER || self.crypto.kdf != KEYSTORE_KDF {
+            return Err(WormholeKeystoreError::UnsupportedCrypto);
+        }
+        if self.crypto.cipherparams.nonce.len() != 12 {
+            return Err(WormholeKeystoreError::InvalidCipherParams);
+        }
+
+        let key = self.crypto.kdfparams.derive_key(password)?;
+        let aad = associated_data(self.address, &self.params);
+        let plaintext: Vec<u8> = Aes256Gcm::new((&*key).into())
+            .decrypt(
+                Nonce::from_slice(&self.crypto.cipherparams.nonce),
+                Payload { msg: &self.crypto.ciphertext, aad: &aad },
+            )
+            .map_err(|_| WormholeKeystoreError::Decryption)?;
+
+        let secret = WormholeSecret::new_unchecked(plaintext.into());
+        if !secret.is_valid_with_params(&self.params) {
+            return Err(WormholeKeystoreError::InvalidSecret);
+        }
+        let address = secret.burn_address_with_params(&self.params);
+        if address != self.address {
+            return Err(WormholeKeystoreError::AddressMismatch {
+                expected: self.address,
+                got: address,
+            });
+        }
+        Ok(secret)
+    }
+}
+
+/// Returns the associated data authenticated alongside the encrypted secret.
+/// `address + rlp(params)`
+fn associated_data(address: Address, params: &WormholeParams) -> Vec<u8> {
+    let mut aad = address.to_vec();
+    alloy_rlp::Encodable::encode(params, &mut aad);
+    aad
+}
+
+/// The error returned by [`WormholeKeystore`].
+#[derive(PartialEq, Eq, Debug)]
+pub enum WormholeKeystoreError {
+    /// The keystore version is not supported.
+    UnsupportedVersion(u8),
+    /// The keystore cipher or key derivation function is not supported.
+    UnsupportedCrypto,
+    /// The cipher parameters are invalid.
+    InvalidCipherParams,
+    /// The key derivation function parameters are invalid.
+    InvalidKdfParams,
+    /// The secret encryption failed.
+    Encryption,
+    /// The secret decryption failed, i.e. the password is wrong or the keystore is corrupted.
+    Decryption,
+    /// The decrypted secret is not valid.
+    InvalidSecret,
+    /// The burn address of the decrypted secret does not match the keystore address.
+    AddressMismatch {
+        /// The keystore address.
+        expected: Address,
+        /// The burn address of the decrypted secret.
+        got: Address,
+    },
+    /// The random number generation failed.
+    Random(getrandom::Error),
+}
+
+impl core::error::Error for WormholeKeystoreError {}
+
+impl fmt::Display for WormholeKeystoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported keystore version {version}")
+            }
+            Self::UnsupportedCrypto => write!(f, "unsupported keystore cipher or kdf"),
+            Self::InvalidCipherParams => write!(f, "invalid cipher params"),
+            Self::InvalidKdfParams => write!(f, "invalid kdf params"),
+            Self::Encryption => write!(f, "encryption failed"),
+            Self::Decryption => {
+                write!(f, "decryption failed: wrong password or corrupted keystore")
+            }
+            Self::InvalidSecret => write!(f, "invalid secret"),
+            Self::AddressMismatch { expected, got } => {
+                write!(f, "address mismatch: expected {expected}, got {got}")
+            }
+            Self::Random(error) => write!(f, "random: {error}"),
+        }
+    }
+}
+
+impl From<getrandom::Error> for WormholeKeystoreError {
+    fn from(error: getrandom::Error) -> Self {
+        Self::Random(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secret::TEST_SECRET;
+
+    const PASSWORD: &[u8] = b"password";
+
+    fn keystore() -> WormholeKeystore {
+        let kdfparams =
+            KeystoreKdfParams { n: 1 << 4, ..KeystoreKdfParams::recommended(B256::ZERO) };
+        WormholeKeystore::encrypt_with(
+            &TEST_SECRET,
+            WormholeParams::DEFAULT,
+            PASSWORD,
+            kdfparams,
+            [0u8; 12],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn encrypt_decrypt() {
+        let keystore = keystore().with_label("test");
+        assert_eq!(keystore.address, TEST_SECRET.burn_address());
+        assert_eq!(keystore.decrypt(PASSWORD), Ok(TEST_SECRET));
+        assert_eq!(keystore.decrypt(b"wrong"), Err(WormholeKeystoreError::Decryption));
+
+        let json = serde_json::to_string(&keystore).unwrap();
+        let decoded: WormholeKeystore = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, keystore);
+        assert_eq!(decoded.decrypt(PASSWORD), Ok(TEST_SECRET));
+    }
+
+    #[test]
+    fn tampered_keystore() {
+        let mut keystore = keystore();
+        keystore.address = Address::ZERO;
+        assert_eq!(keystore.decrypt(PASSWORD), Err(WormholeKeystoreError::Decryption));
+
+        let mut keystore = self::keystore();
+        keystore.params = keystore.params.with_pow_log_difficulty(8);
+        assert_eq!(keystore.decrypt(PASSWORD), Err(WormholeKeystoreError::Decryption));
+
+        let mut keystore = self::keystore();
+        keystore.crypto.kdfparams.n = 3;
+        assert_eq!(keystore.decrypt(PASSWORD), Err(WormholeKeystoreError::InvalidKdfParams));
+
+        let mut keystore = self::keystore();
+        keystore.version = 2;
+        assert_eq!(keystore.decrypt(PASSWORD), Err(WormholeKeystoreError::UnsupportedVersion(2)));
+    }
+}
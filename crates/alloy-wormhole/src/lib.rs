@@ -3,6 +3,7 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 extern crate alloc;
 
+use alloc::vec::Vec;
 use alloy_consensus::{
     transaction::{RlpEcdsaDecodableTx, RlpEcdsaEncodableTx, SignableTransaction},
     Transaction, Typed2718,
@@ -16,9 +17,33 @@ use core::mem;
 mod constants;
 pub use constants::*;
 
+mod error;
+pub use error::WormholeTxError;
+
+pub mod nullifier;
+
+mod output;
+pub use output::{WormholeAggregationOutput, WormholeNullifierOutput, WormholeProgramOutput};
+
+mod params;
+pub use params::WormholeParams;
+
+mod proof_system;
+pub use proof_system::WormholeProofSystem;
+
 pub mod secret;
 pub use secret::WormholeSecret;
 
+#[cfg(feature = "keystore")]
+pub mod keystore;
+#[cfg(feature = "keystore")]
+pub use keystore::{WormholeKeystore, WormholeKeystoreError};
+
+pub mod validation;
+pub use validation::{
+    validate_wormhole_tx, WormholeStateProvider, WormholeTxProofVerifier, WormholeTxValidationError,
+};
+
 #[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
@@ -62,6 +87,8 @@ pub struct WormholeTx {
     #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
     pub max_priority_fee_per_gas: u128,
     /// The 160-bit address of the message call’s recipient.
+    ///
+    /// This is also the beneficiary of the withdrawn amount and must match the proof recipient.
     pub to: Address,
     /// Input has two uses depending if transaction is Create or Call (if `to` field is None or
     /// Some). pub init: An unlimited size byte array specifying the
@@ -102,7 +129,29 @@ impl WormholeTx {
         self.input.len() + // input      
         self.access_list.size() + // access_list
         mem::size_of::<u64>() + // proof_block_number
-        mem::size_of::<WormholeTxProof>() // proof
+        mem::size_of::<WormholeTxProof>() + // proof
+        self.proof.nullifiers.len() * mem::size_of::<WormholeNullifierOutput>() // proof nullifiers
+    }
+
+    /// Returns the beneficiary of the withdrawn (minted) amount.
+    #[inline]
+    pub const fn beneficiary(&self) -> Address {
+        self.to
+    }
+
+    /// Validates that the recipient committed to by the proof matches the transaction
+    /// [beneficiary](Self::beneficiary).
+    ///
+    /// This binds the proof to the transaction and prevents it from being replayed with a
+    /// different beneficiary.
+    pub fn validate_recipient(&self) -> Result<(), WormholeTxError> {
+        if self.proof.recipient != self.beneficiary() {
+            return Err(WormholeTxError::RecipientMismatch {
+                expected: self.beneficiary(),
+                got: self.proof.recipient,
+            });
+        }
+        Ok(())
     }
 }
 
@@ -314,16 +363,87 @@ impl Decodable for WormholeTx {
 #[derive(Clone, Debug, Default, PartialEq, Eq, Hash, RlpEncodable, RlpDecodable)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct WormholeTxProof {
+    /// The address of the nullifier system contract.
+    pub nullifier_address: Address,
     /// The state root of the block number deposit was validated against.
     pub state_root: B256,
-    /// The nullifier part of the program output.
-    pub nullifier: B256,
-    /// The withdraw (mint) value.
-    pub withdraw_value: u128,
+    /// The total withdraw (mint) value.
+    pub withdraw_value: U256,
+    /// The nullifiers part of the program output, one per withdrawal.
+    pub nullifiers: Vec<WormholeNullifierOutput>,
+    /// The recipient of the withdraw (mint) value.
+    pub recipient: Address,
+    /// The relayer that receives the relayer fee.
+    pub relayer: Address,
+    /// The fee paid to the relayer out of the withdraw (mint) value.
+    pub relayer_fee: U256,
+    /// The protocol parameters the proof was generated with.
+    pub params: WormholeParams,
+    /// The proof system that produced the proof.
+    pub proof_system: WormholeProofSystem,
+    /// The commitment to the program the proof is for,
+    /// i.e. the verifying key hash (SP1, Pico) or the image ID (Risc0).
+    pub program_id: B256,
     /// The ZK proof of the program execution.
     pub proof: Bytes,
 }
 
+impl WormholeTxProof {
+    /// Creates new [`WormholeTxProof`] from the program output and the proof of its execution.
+    pub fn new(
+        output: WormholeProgramOutput,
+        proof_system: WormholeProofSystem,
+        program_id: B256,
+        proof: Bytes,
+    ) -> Self {
+        Self {
+            nullifier_address: output.nullifier_address,
+            state_root: output.state_root,
+            withdraw_value: output.withdraw_amount,
+            nullifiers: output.nullifiers,
+            recipient: output.recipient,
+            relayer: output.relayer,
+            relayer_fee: output.relayer_fee,
+            params: output.params,
+            proof_system,
+            program_id,
+            proof,
+        }
+    }
+
+    /// Returns the program output the proof attests to.
+    pub fn output(&self) -> WormholeProgramOutput {
+        WormholeProgramOutput {
+            nullifier_address: self.nullifier_address,
+            state_root: self.state_root,
+            withdraw_amount: self.withdraw_value,
+            nullifiers: self.nullifiers.clone(),
+            recipient: self.recipient,
+            relayer: self.relayer,
+            relayer_fee: self.relayer_fee,
+            params: self.params,
+        }
+    }
+
+    /// Returns the public values the proof must commit to.
+    /// See [`WormholeProgramOutput::public_values`].
+    pub fn public_values(&self) -> Vec<u8> {
+        self.output().public_values()
+    }
+
+    /// Returns the value minted to the recipient, i.e. the withdraw value less the relayer fee.
+    #[inline]
+    pub fn recipient_value(&self) -> U256 {
+        self.withdraw_value.saturating_sub(self.relayer_fee)
+    }
+}
+
+impl From<&WormholeTxProof> for WormholeProgramOutput {
+    fn from(proof: &WormholeTxProof) -> Self {
+        proof.output()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -332,7 +452,7 @@ mod tests {
     #[test]
     fn encode_decode_wormholetx() {
         let hash: B256 =
-            b256!("0xa71451f735e5888c5bf6e5a16f0f9f2d4a003ed103987b510e312b57205e7c7b");
+            b256!("0x741c7702bd5dfa7118dae6218c052456b93a786462233244b2b2035c50884edb");
 
         let tx =  WormholeTx {
                 chain_id: 1,
@@ -359,4 +479,73 @@ mod tests {
         assert_eq!(decoded, tx.into_signed(sig));
         assert_eq!(*decoded.hash(), hash);
     }
+
+    #[test]
+    fn validate_wormholetx_recipient() {
+        let recipient = address!("6069a6c32cf691f5982febae4faf8a6f3ab2f0f6");
+        let mut tx = WormholeTx {
+            to: recipient,
+            proof: WormholeTxProof { recipient, ..Default::default() },
+            ..Default::default()
+        };
+        assert_eq!(tx.validate_recipient(), Ok(()));
+
+        tx.to = Address::with_last_byte(1);
+        assert_eq!(
+            tx.validate_recipient(),
+            Err(WormholeTxError::RecipientMismatch { expected: tx.to, got: recipient })
+        );
+    }
+
+    #[test]
+    fn wormholetx_proof_output_roundtrip() {
+        let output = WormholeProgramOutput {
+            nullifier_address: address!("ce8f0b46cc1527f27429938d3cc85bf7d270a8f6"),
+            state_root: b256!("0x153a3b2082ce10f2c9e421ac684d1d27a96af410000bf94bb986ed227d566cf0"),
+            withdraw_amount: U256::MAX,
+            nullifiers: vec![
+                WormholeNullifierOutput {
+                    nullifier: b256!(
+                        "0xb3f99dab37ecdef88863af5231ae2b72faa95793ff88ed07de9c4e58315f6447"
+                    ),
+                    cumulative_withdrawn_amount_hashed: B256::with_last_byte(1),
+                    cumulative_withdrawn_amount_encrypted: B256::with_last_byte(4),
+                },
+                WormholeNullifierOutput {
+                    nullifier: B256::with_last_byte(2),
+                    cumulative_withdrawn_amount_hashed: B256::with_last_byte(3),
+                    cumulative_withdrawn_amount_encrypted: B256::with_last_byte(5),
+                },
+            ],
+            recipient: address!("6069a6c32cf691f5982febae4faf8a6f3ab2f0f6"),
+            relayer: Address::with_last_byte(1),
+            relayer_fee: U256::from(1),
+            params: WormholeParams::DEFAULT.with_pow_log_difficulty(8),
+        };
+        let proof = WormholeTxProof::new(
+            output.clone(),
+            WormholeProofSystem::SP1_V4_GROTH16,
+            B256::with_last_byte(2),
+            Bytes::from_static(&[0x1]),
+        );
+        assert_eq!(proof.output(), output);
+        assert_eq!(proof.recipient_value(), U256::MAX - U256::from(1));
+
+        let public_values = proof.public_values();
+        assert_eq!(public_values, output.public_values());
+        assert_eq!(WormholeProgramOutput::from_public_values(&public_values), Ok(output));
+    }
+
+    #[test]
+    fn encode_decode_unknown_proof_system() {
+        let proof = WormholeTxProof {
+            proof_system: WormholeProofSystem::new(0xff),
+            program_id: B256::with_last_byte(1),
+            ..Default::default()
+        };
+        assert!(!proof.proof_system.is_known());
+
+        let encoded = alloy_rlp::encode(&proof);
+        assert_eq!(WormholeTxProof::decode(&mut &encoded[..]), Ok(proof));
+    }
 }
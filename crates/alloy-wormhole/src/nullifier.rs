@@ -0,0 +1,246 @@
+//! Wormhole nullifier chain.
+//!
+//! Each withdrawal from the burn address stores the hash of the cumulative withdrawn amount
+//! (including the withdrawal) in the nullifier contract at the slot equal to the withdrawal
+//! nullifier. The next withdrawal proves the inclusion of the previous nullifier along with the
+//! cumulative withdrawn amount it starts from.
+//!
+//! The cumulative withdrawn amount encrypted under the key derived from the secret is stored at
+//! [`encrypted_amount_slot`], so that the withdrawal history can be reconstructed from the chain
+//! state alone. See [`WormholeSecret::decrypt_cumulative_withdrawn_amount`].
+
+use crate::{WormholeParams, WormholeSecret};
+use alloy_primitives::{keccak256, B256, U256};
+use core::fmt;
+
+/// The maximum number of significant decimal digits of the withdraw amount tried during
+/// the cumulative withdrawn amount recovery.
+pub const RECOVERY_SIGNIFICANT_DIGITS: u32 = 3;
+
+/// Returns the hash of the cumulative withdrawn amount stored in the nullifier slot.
+/// `keccak256(uint256_be(cumulative_withdrawn_amount))`
+pub fn cumulative_withdrawn_amount_hash(cumulative_withdrawn_amount: U256) -> B256 {
+    keccak256(B256::new(cumulative_withdrawn_amount.to_be_bytes()))
+}
+
+/// Returns the storage slot of the encrypted cumulative withdrawn amount stored alongside provided
+/// nullifier.
+/// `keccak256(nullifier)`
+pub fn encrypted_amount_slot(nullifier: B256) -> B256 {
+    keccak256(nullifier)
+}
+
+/// Recovers the cumulative withdrawn amount from the hash stored in the nullifier slot, given the
+/// cumulative withdrawn amount before the withdrawal and the deposit amount.
+///
+/// Since the hash cannot be inverted, the withdraw amount is searched among the amounts with up to
+/// [`RECOVERY_SIGNIFICANT_DIGITS`] significant decimal digits and the remainder of the deposit.
+/// Returns [`None`] if none of the candidates matches the hash.
+pub fn recover_cumulative_withdrawn_amount(
+    hash: B256,
+    previous_cumulative_withdrawn_amount: U256,
+    deposit_amount: U256,
+) -> Option<U256> {
+    let remainder = deposit_amount.checked_sub(previous_cumulative_withdrawn_amount)?;
+    if cumulative_withdrawn_amount_hash(deposit_amount) == hash {
+        return Some(deposit_amount);
+    }
+
+    let max_mantissa = 10u64.pow(RECOVERY_SIGNIFICANT_DIGITS);
+    let mut unit = U256::from(1);
+    while unit <= remainder {
+        for mantissa in 1..max_mantissa {
+            let withdraw_amount = unit * U256::from(mantissa);
+            if withdraw_amount > remainder {
+                break;
+            }
+            let cumulative_withdrawn_amount =
+                previous_cumulative_withdrawn_amount + withdraw_amount;
+            if cumulative_withdrawn_amount_hash(cumulative_withdrawn_amount) == hash {
+                return Some(cumulative_withdrawn_amount);
+            }
+        }
+        unit *= U256::from(10);
+    }
+    None
+}
+
+/// The position in the nullifier chain the next withdrawal starts from.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct NullifierChainState {
+    /// The index of the next withdrawal, i.e. the number of spent nullifiers.
+    pub withdrawal_index: U256,
+    /// The cumulative withdrawn amount before the next withdrawal.
+    pub cumulative_withdrawn_amount: U256,
+}
+
+/// Walks the nullifier chain of the secret until the first nullifier that is not stored in the
+/// nullifier contract. The `storage` returns the value of the nullifier contract slot or [`None`]
+/// if it's unknown.
+///
+/// The cumulative withdrawn amount is decrypted from the value stored at
+/// [`encrypted_amount_slot`] of each spent nullifier. Otherwise, the provided `hint` is tried and
+/// the amount is recovered with [`recover_cumulative_withdrawn_amount`].
+pub fn walk_nullifier_chain(
+    secret: &WormholeSecret,
+    params: &WormholeParams,
+    deposit_amount: U256,
+    hint: Option<U256>,
+    storage: impl Fn(B256) -> Option<U256>,
+) -> Result<NullifierChainState, NullifierChainError> {
+    let mut withdrawal_index = U256::ZERO;
+    let mut cumulative_withdrawn_amount = Some(U256::ZERO);
+    loop {
+        let nullifier = secret.nullifier_with_params(params, withdrawal_index);
+        let value = storage(nullifier)
+            .ok_or(NullifierChainError::MissingNullifier { withdrawal_index, nullifier })?;
+        if value.is_zero() {
+            break;
+        }
+
+        let hash = B256::new(value.to_be_bytes());
+        let encrypted = storage(encrypted_amount_slot(nullifier)).unwrap_or_default();
+        let decrypted = secret
+            .decrypt_cumulative_withdrawn_amount(nullifier, B256::new(encrypted.to_be_bytes()));
+        cumulative_withdrawn_amount = core::iter::once(decrypted)
+            .chain(hint)
+            .find(|amount| cumulative_withdrawn_amount_hash(*amount) == hash)
+            .or_else(|| {
+                recover_cumulative_withdrawn_amount(
+                    hash,
+                    cumulative_withdrawn_amount?,
+                    deposit_amount,
+                )
+            });
+        withdrawal_index += U256::from(1);
+    }
+
+    let cumulative_withdrawn_amount = cumulative_withdrawn_amount
+        .ok_or(NullifierChainError::UnrecoverableAmount { withdrawal_index })?;
+    Ok(NullifierChainState { withdrawal_index, cumulative_withdrawn_amount })
+}
+
+/// The error returned by [`walk_nullifier_chain`].
+#[derive(PartialEq, Eq, Debug)]
+pub enum NullifierChainError {
+    /// The value of the nullifier slot is unknown.
+    MissingNullifier {
+        /// The withdrawal index of the nullifier.
+        withdrawal_index: U256,
+        /// The nullifier.
+        nullifier: B256,
+    },
+    /// The cumulative withdrawn amount can neither be decrypted nor recovered, e.g. the withdraw
+    /// amount has more than [`RECOVERY_SIGNIFICANT_DIGITS`] significant digits.
+    UnrecoverableAmount {
+        /// The index of the next withdrawal.
+        withdrawal_index: U256,
+    },
+}
+
+impl core::error::Error for NullifierChainError {}
+
+impl fmt::Display for NullifierChainError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingNullifier { withdrawal_index, nullifier } => {
+                write!(f, "missing nullifier {nullifier} at withdrawal index {withdrawal_index}")
+            }
+            Self::UnrecoverableAmount { withdrawal_index } => write!(
+                f,
+                "unable to recover the cumulative withdrawn amount at withdrawal index \
+                 {withdrawal_index}"
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{secret::TEST_SECRET, MAX_DEPOSIT};
+    use alloc::collections::BTreeMap;
+
+    #[test]
+    fn recover_cumulative_withdrawn_amount() {
+        let ether = U256::from(10).pow(U256::from(18));
+        let previous = ether / U256::from(4);
+
+        for withdraw_amount in
+            [U256::from(1), ether * U256::from(3) / U256::from(2), MAX_DEPOSIT - previous]
+        {
+            let cumulative = previous + withdraw_amount;
+            assert_eq!(
+                super::recover_cumulative_withdrawn_amount(
+                    cumulative_withdrawn_amount_hash(cumulative),
+                    previous,
+                    MAX_DEPOSIT
+                ),
+                Some(cumulative)
+            );
+        }
+
+        // Too many significant digits.
+        let cumulative = previous + U256::from(1234);
+        assert_eq!(
+            super::recover_cumulative_withdrawn_amount(
+                cumulative_withdrawn_amount_hash(cumulative),
+                previous,
+                MAX_DEPOSIT
+            ),
+            None
+        );
+        // Exceeds the deposit.
+        assert_eq!(
+            super::recover_cumulative_withdrawn_amount(
+                cumulative_withdrawn_amount_hash(MAX_DEPOSIT + U256::from(1)),
+                previous,
+                MAX_DEPOSIT
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn walk_nullifier_chain() {
+        let params = WormholeParams::DEFAULT;
+        let ether = U256::from(10).pow(U256::from(18));
+        let nullifier = |index: u64| TEST_SECRET.nullifier_with_params(&params, U256::from(index));
+        let walk = |storage: &BTreeMap<B256, U256>, hint| {
+            super::walk_nullifier_chain(&TEST_SECRET, &params, MAX_DEPOSIT, hint, |slot| {
+                storage.get(&slot).copied()
+            })
+        };
+
+        // The first withdrawal is recoverable, the second one has too many significant digits.
+        let first = ether / U256::from(4);
+        let second = first + U256::from(1234);
+        let mut storage = BTreeMap::from_iter([
+            (nullifier(0), cumulative_withdrawn_amount_hash(first).into()),
+            (nullifier(1), cumulative_withdrawn_amount_hash(second).into()),
+        ]);
+        assert_eq!(
+            walk(&storage, None),
+            Err(NullifierChainError::MissingNullifier {
+                withdrawal_index: U256::from(2),
+                nullifier: nullifier(2)
+            })
+        );
+
+        storage.insert(nullifier(2), U256::ZERO);
+        assert_eq!(
+            walk(&storage, None),
+            Err(NullifierChainError::UnrecoverableAmount { withdrawal_index: U256::from(2) })
+        );
+        let state = NullifierChainState {
+            withdrawal_index: U256::from(2),
+            cumulative_withdrawn_amount: second,
+        };
+        assert_eq!(walk(&storage, Some(second)), Ok(state));
+
+        // The encrypted amount is decrypted regardless of its significant digits.
+        let encrypted = TEST_SECRET.encrypt_cumulative_withdrawn_amount(nullifier(1), second);
+        storage.insert(encrypted_amount_slot(nullifier(1)), encrypted.into());
+        assert_eq!(walk(&storage, None), Ok(state));
+    }
+}
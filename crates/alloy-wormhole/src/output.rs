@@ -0,0 +1,133 @@
+use crate::WormholeParams;
+use alloc::vec::Vec;
+use alloy_primitives::{Address, B256, U256};
+use alloy_rlp::{Decodable, RlpDecodable, RlpEncodable};
+
+/// The output of the Wormhole program.
+///
+/// The program commits to the [public values](Self::public_values) of the output, which can be
+/// recomputed from the [`WormholeTxProof`](crate::WormholeTxProof) alone.
+///
+/// A single proof may cover withdrawals from multiple burn addresses, in which case the output
+/// contains the total withdraw amount and one nullifier per withdrawal.
+#[derive(PartialEq, Eq, Clone, Debug, Default, RlpEncodable, RlpDecodable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WormholeProgramOutput {
+    /// The address of the nullifier system contract.
+    pub nullifier_address: Address,
+    /// The state root of the block to validate against provided as part of the input.
+    pub state_root: B256,
+    /// The total withdraw amount of all withdrawals.
+    pub withdraw_amount: U256,
+    /// The nullifiers of the withdrawals.
+    pub nullifiers: Vec<WormholeNullifierOutput>,
+    /// The recipient of the withdrawn amount provided as part of the input.
+    pub recipient: Address,
+    /// The relayer provided as part of the input.
+    pub relayer: Address,
+    /// The relayer fee provided as part of the input.
+    pub relayer_fee: U256,
+    /// The protocol parameters the program was executed with.
+    pub params: WormholeParams,
+}
+
+/// The nullifier of a single withdrawal committed to by the Wormhole program.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, Hash, RlpEncodable, RlpDecodable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WormholeNullifierOutput {
+    /// The nullifier the withdrawal is for.
+    pub nullifier: B256,
+    /// The keccak256 of cumulative withdrawn amount including the withdrawal, stored in the
+    /// nullifier slot.
+    pub cumulative_withdrawn_amount_hashed: B256,
+    /// The cumulative withdrawn amount including the withdrawal encrypted under the key derived
+    /// from the secret, stored in the encrypted amount slot of the nullifier.
+    pub cumulative_withdrawn_amount_encrypted: B256,
+}
+
+impl WormholeProgramOutput {
+    /// Returns the amount minted to the recipient, i.e. the withdraw amount less the relayer fee.
+    pub fn recipient_amount(&self) -> U256 {
+        self.withdraw_amount.saturating_sub(self.relayer_fee)
+    }
+
+    /// Returns the public values the program commits to, i.e. the RLP encoding of the output.
+    pub fn public_values(&self) -> Vec<u8> {
+        alloy_rlp::encode(self)
+    }
+
+    /// Decodes the output from the public values committed to by the program.
+    pub fn from_public_values(mut public_values: &[u8]) -> alloy_rlp::Result<Self> {
+        let output = Self::decode(&mut public_values)?;
+        if !public_values.is_empty() {
+            return Err(alloy_rlp::Error::UnexpectedLength);
+        }
+        Ok(output)
+    }
+}
+
+/// The output of the Wormhole aggregation program.
+///
+/// The aggregation program recursively verifies the proofs of the Wormhole program and commits to
+/// their outputs, so that a single proof covers all of them.
+#[derive(PartialEq, Eq, Clone, Debug, Default, RlpEncodable, RlpDecodable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WormholeAggregationOutput {
+    /// The commitment to the Wormhole program the aggregated proofs are for, as verified by the
+    /// aggregation program. See [`Self::program_id_from_words`].
+    pub program_id: B256,
+    /// The outputs of the aggregated proofs.
+    pub outputs: Vec<WormholeProgramOutput>,
+}
+
+impl WormholeAggregationOutput {
+    /// Returns the program commitment from the digest words of the verifying key (SP1) or the
+    /// image ID (Risc0). The words are encoded in little-endian byte order.
+    pub fn program_id_from_words(words: [u32; 8]) -> B256 {
+        let mut program_id = B256::ZERO;
+        for (chunk, word) in program_id.chunks_exact_mut(4).zip(words) {
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+        program_id
+    }
+
+    /// Returns the public values the aggregation program commits to, i.e. the RLP encoding of
+    /// the output.
+    pub fn public_values(&self) -> Vec<u8> {
+        alloy_rlp::encode(self)
+    }
+
+    /// Decodes the output from the public values committed to by the aggregation program.
+    pub fn from_public_values(mut public_values: &[u8]) -> alloy_rlp::Result<Self> {
+        let output = Self::decode(&mut public_values)?;
+        if !public_values.is_empty() {
+            return Err(alloy_rlp::Error::UnexpectedLength);
+        }
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn aggregation_output_roundtrip() {
+        let output = WormholeAggregationOutput {
+            program_id: WormholeAggregationOutput::program_id_from_words([1, 2, 3, 4, 5, 6, 7, 8]),
+            outputs: vec![
+                WormholeProgramOutput { withdraw_amount: U256::from(1), ..Default::default() },
+                WormholeProgramOutput { withdraw_amount: U256::from(2), ..Default::default() },
+            ],
+        };
+        assert_eq!(output.program_id[..8], [1, 0, 0, 0, 2, 0, 0, 0]);
+
+        let public_values = output.public_values();
+        assert_eq!(WormholeAggregationOutput::from_public_values(&public_values), Ok(output));
+        assert!(WormholeAggregationOutput::from_public_values(
+            &[public_values, vec![0x0]].concat()
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,91 @@
+use crate::{MAGIC_ADDRESS, MAGIC_NULLIFIER, MAGIC_POW, MAX_DEPOSIT, POW_LOG_DIFFICULTY};
+use alloy_primitives::U256;
+use alloy_rlp::{RlpDecodable, RlpEncodable};
+
+/// The Wormhole protocol parameters.
+///
+/// The program commits to the parameters it was executed with, so that the verifier can check them
+/// against the parameters of the chain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, RlpEncodable, RlpDecodable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WormholeParams {
+    /// The salt byte for generating the magic burn address.
+    pub magic_address: u8,
+    /// The salt byte for computing the nullifier.
+    pub magic_nullifier: u8,
+    /// The salt byte for Proof-of-Work condition on the secret.
+    pub magic_pow: u8,
+    /// The exponent for Proof-of-Work condition on the secret.
+    pub pow_log_difficulty: u8,
+    /// The maximum allowed value of the Wormhole deposit.
+    pub max_deposit: U256,
+}
+
+impl Default for WormholeParams {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl WormholeParams {
+    /// The default Wormhole parameters.
+    pub const DEFAULT: Self = Self {
+        magic_address: MAGIC_ADDRESS,
+        magic_nullifier: MAGIC_NULLIFIER,
+        magic_pow: MAGIC_POW,
+        pow_log_difficulty: POW_LOG_DIFFICULTY,
+        max_deposit: MAX_DEPOSIT,
+    };
+
+    /// Sets the exponent for Proof-of-Work condition on the secret.
+    pub const fn with_pow_log_difficulty(mut self, pow_log_difficulty: u8) -> Self {
+        self.pow_log_difficulty = pow_log_difficulty;
+        self
+    }
+
+    /// Sets the maximum allowed value of the Wormhole deposit.
+    pub const fn with_max_deposit(mut self, max_deposit: U256) -> Self {
+        self.max_deposit = max_deposit;
+        self
+    }
+
+    /// Returns the u256 Proof-of-Work condition on the secret.
+    /// `2 ** pow_log_difficulty`
+    pub fn pow_difficulty(&self) -> U256 {
+        U256::from(1) << self.pow_log_difficulty as usize
+    }
+
+    /// Returns the maximum total amount that can be withdrawn from the deposit.
+    /// The deposit amount in excess of [`max_deposit`](Self::max_deposit) cannot be withdrawn.
+    #[inline]
+    pub fn max_withdrawable_amount(&self, deposit_amount: U256) -> U256 {
+        deposit_amount.min(self.max_deposit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::POW_DIFFICULTY_U256;
+
+    #[test]
+    fn pow_difficulty() {
+        assert_eq!(WormholeParams::DEFAULT.pow_difficulty(), POW_DIFFICULTY_U256);
+        assert_eq!(
+            WormholeParams::DEFAULT.with_pow_log_difficulty(8).pow_difficulty(),
+            U256::from(256)
+        );
+        assert_eq!(
+            WormholeParams::DEFAULT.with_pow_log_difficulty(255).pow_difficulty(),
+            U256::from(1) << 255
+        );
+    }
+
+    #[test]
+    fn max_withdrawable_amount() {
+        let params = WormholeParams::DEFAULT;
+        assert_eq!(params.max_withdrawable_amount(U256::from(1)), U256::from(1));
+        assert_eq!(params.max_withdrawable_amount(MAX_DEPOSIT), MAX_DEPOSIT);
+        assert_eq!(params.max_withdrawable_amount(U256::MAX), MAX_DEPOSIT);
+    }
+}
@@ -0,0 +1,58 @@
+use alloy_rlp::{RlpDecodableWrapper, RlpEncodableWrapper};
+use core::fmt;
+
+/// The versioned identifier of the proof system that produced the
+/// [`WormholeTxProof`](crate::WormholeTxProof).
+///
+/// Unknown identifiers are decodable, it is up to the verifier to reject them.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    RlpEncodableWrapper,
+    RlpDecodableWrapper,
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct WormholeProofSystem(u8);
+
+impl WormholeProofSystem {
+    /// SP1 v4 Groth16 proof.
+    pub const SP1_V4_GROTH16: Self = Self(0x01);
+    /// Risc0 v2 receipt.
+    pub const RISC0_V2: Self = Self(0x02);
+    /// Pico v1 proof.
+    pub const PICO_V1: Self = Self(0x03);
+
+    /// Create new [`WormholeProofSystem`] from raw identifier.
+    pub const fn new(id: u8) -> Self {
+        Self(id)
+    }
+
+    /// Returns the raw identifier.
+    pub const fn id(self) -> u8 {
+        self.0
+    }
+
+    /// Returns `true` if the identifier is one of the known proof systems.
+    pub const fn is_known(self) -> bool {
+        matches!(self, Self::SP1_V4_GROTH16 | Self::RISC0_V2 | Self::PICO_V1)
+    }
+}
+
+impl fmt::Display for WormholeProofSystem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::SP1_V4_GROTH16 => write!(f, "sp1-v4-groth16"),
+            Self::RISC0_V2 => write!(f, "risc0-v2"),
+            Self::PICO_V1 => write!(f, "pico-v1"),
+            Self(id) => write!(f, "unknown({id:#04x})"),
+        }
+    }
+}
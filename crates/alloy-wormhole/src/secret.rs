@@ -1,13 +1,53 @@
-use crate::{constants::MAGIC_NULLIFIER, MAGIC_ADDRESS, MAGIC_POW, POW_DIFFICULTY_U256};
+use crate::{
+    WormholeParams, AMOUNT_ENCRYPTION_DOMAIN, NULLIFIER_DOMAIN, NULLIFIER_VERSION, SECRET_LENGTH,
+};
+use alloc::vec::Vec;
 use alloy_primitives::{bytes::BytesMut, Address, Bytes, B256, U256};
-use core::ops::Rem;
+use core::{fmt, ops::Rem};
 use derive_more::AsRef;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+mod hd;
+pub use hd::WormholeMasterSeed;
+
+#[cfg(feature = "std")]
+mod miner;
+#[cfg(feature = "std")]
+pub use miner::{MiningCancellationHandle, MiningProgress, WormholeSecretMiner};
 
 /// The secret preimage for burn address.
-#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, AsRef)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+///
+/// The [`Debug`](fmt::Debug) and [`Display`](fmt::Display) output is redacted and the memory is
+/// zeroized on drop of the last reference to the underlying bytes. The secret does not implement
+/// serde traits, the serialization must be explicitly enabled with
+/// `#[serde(with = "alloy_wormhole::secret::serde_secret")]`.
+#[derive(Clone, Default, PartialEq, Eq, Hash, AsRef)]
 pub struct WormholeSecret(Bytes);
 
+impl fmt::Debug for WormholeSecret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("WormholeSecret").field(&"<redacted>").finish()
+    }
+}
+
+impl fmt::Display for WormholeSecret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("<redacted>")
+    }
+}
+
+impl Drop for WormholeSecret {
+    fn drop(&mut self) {
+        // Shared and static bytes can not be reclaimed. The former are zeroized on drop of the
+        // last clone.
+        if let Ok(mut bytes) = core::mem::take(&mut self.0).0.try_into_mut() {
+            bytes.zeroize();
+        }
+    }
+}
+
+impl ZeroizeOnDrop for WormholeSecret {}
+
 impl TryFrom<Bytes> for WormholeSecret {
     type Error = Self;
 
@@ -47,53 +87,184 @@ impl WormholeSecret {
     /// [`getrandom_uninit`](getrandom::getrandom_uninit).
     #[inline]
     pub fn try_random() -> Result<Self, getrandom::Error> {
-        let mut bytes = BytesMut::zeroed(32); // TODO: reconsider or justify secret length
+        Self::try_random_with_params(&WormholeParams::DEFAULT)
+    }
+
+    /// Tries to mine for a new [`WormholeSecret`] with cryptographically random content that is
+    /// **valid** under provided parameters.
+    ///
+    /// # Errors
+    ///
+    /// This function only propagates the error from the underlying call to
+    /// [`getrandom_uninit`](getrandom::getrandom_uninit).
+    pub fn try_random_with_params(params: &WormholeParams) -> Result<Self, getrandom::Error> {
+        let mut bytes = BytesMut::zeroed(SECRET_LENGTH);
+        getrandom::getrandom(&mut bytes)?;
         loop {
-            getrandom::getrandom(&mut bytes)?;
-            if is_valid_wormhole_secret(&bytes) {
+            if is_valid_wormhole_secret(params, &bytes) {
                 return Ok(Self(bytes.freeze().into()));
             }
+            next_secret_candidate(&mut bytes);
         }
     }
 
+    /// Returns `true` if the secret is exactly [`SECRET_LENGTH`] bytes long.
+    pub fn has_valid_length(&self) -> bool {
+        self.0.len() == SECRET_LENGTH
+    }
+
     /// Returns `true` if the secret is valid.
     pub fn is_valid(&self) -> bool {
-        is_valid_wormhole_secret(&self.0)
+        self.is_valid_with_params(&WormholeParams::DEFAULT)
+    }
+
+    /// Returns `true` if the secret is valid under provided parameters.
+    pub fn is_valid_with_params(&self, params: &WormholeParams) -> bool {
+        is_valid_wormhole_secret(params, &self.0)
     }
 
     /// Returns Proof-of-Work hash for this secret.
     /// `sha256(MAGIC_POW + secret)`
     pub fn proof_of_work_hash(&self) -> B256 {
-        proof_of_work_secret_hash(&self.0)
+        proof_of_work_secret_hash(&WormholeParams::DEFAULT, &self.0)
     }
 
     /// Returns the burn address for this secret.
     /// `sha256(MAGIC_ADDRESS + secret)[12:]`
     pub fn burn_address(&self) -> Address {
-        let address_hash = sha256([&[MAGIC_ADDRESS], self.0.as_ref()].concat());
+        self.burn_address_with_params(&WormholeParams::DEFAULT)
+    }
+
+    /// Returns the burn address for this secret under provided parameters.
+    /// `sha256(params.magic_address + secret)[12:]`
+    pub fn burn_address_with_params(&self, params: &WormholeParams) -> Address {
+        let preimage = Zeroizing::new([&[params.magic_address], self.0.as_ref()].concat());
+        let address_hash = sha256(preimage);
         Address::from_slice(&address_hash[12..])
     }
 
     /// Returns the nullifier hash for this secret and provided index.
-    /// `sha256(MAGIC_NULLIFIER + secret + index)`
+    /// See [`wormhole_nullifier`].
     pub fn nullifier(&self, index: U256) -> B256 {
-        sha256([&[MAGIC_NULLIFIER], self.0.as_ref(), index.as_le_slice()].concat())
+        self.nullifier_with_params(&WormholeParams::DEFAULT, index)
+    }
+
+    /// Returns the nullifier hash for this secret and provided index under provided parameters.
+    /// See [`wormhole_nullifier`].
+    pub fn nullifier_with_params(&self, params: &WormholeParams, index: U256) -> B256 {
+        wormhole_nullifier(params, &self.0, index)
+    }
+
+    /// Returns the key the cumulative withdrawn amount stored alongside provided nullifier is
+    /// encrypted with. The nullifier is unique per withdrawal, so the key is never reused.
+    /// `sha256(AMOUNT_ENCRYPTION_DOMAIN + secret + nullifier)`
+    pub fn amount_encryption_key(&self, nullifier: B256) -> B256 {
+        let preimage = Zeroizing::new(
+            [AMOUNT_ENCRYPTION_DOMAIN, self.0.as_ref(), nullifier.as_slice()].concat(),
+        );
+        sha256(preimage)
+    }
+
+    /// Encrypts the cumulative withdrawn amount stored alongside provided nullifier.
+    /// `uint256_be(amount) ^ amount_encryption_key(nullifier)`
+    pub fn encrypt_cumulative_withdrawn_amount(&self, nullifier: B256, amount: U256) -> B256 {
+        B256::new(amount.to_be_bytes()) ^ self.amount_encryption_key(nullifier)
+    }
+
+    /// Decrypts the cumulative withdrawn amount stored alongside provided nullifier.
+    /// `uint256_be(encrypted ^ amount_encryption_key(nullifier))`
+    pub fn decrypt_cumulative_withdrawn_amount(&self, nullifier: B256, encrypted: B256) -> U256 {
+        U256::from_be_bytes((encrypted ^ self.amount_encryption_key(nullifier)).0)
+    }
+}
+
+/// Explicit serde (de)serialization of [`WormholeSecret`] as hex string.
+///
+/// ```ignore
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Input {
+///     #[serde(with = "alloy_wormhole::secret::serde_secret")]
+///     secret: WormholeSecret,
+/// }
+/// ```
+#[cfg(feature = "serde")]
+pub mod serde_secret {
+    use super::WormholeSecret;
+    use alloy_primitives::Bytes;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    /// Serializes the secret as hex string.
+    pub fn serialize<S: Serializer>(
+        secret: &WormholeSecret,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        secret.0.serialize(serializer)
     }
+
+    /// Deserializes the secret from hex string.
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<WormholeSecret, D::Error> {
+        Bytes::deserialize(deserializer).map(WormholeSecret::new_unchecked)
+    }
+}
+
+/// Advances the secret candidate by incrementing the big-endian counter in its last 8 bytes.
+/// The counter wraps around on overflow.
+#[inline]
+fn next_secret_candidate(candidate: &mut [u8]) {
+    let offset = candidate.len() - 8;
+    let counter = &mut candidate[offset..];
+    let next = u64::from_be_bytes((&*counter).try_into().unwrap()).wrapping_add(1);
+    counter.copy_from_slice(&next.to_be_bytes());
+}
+
+/// Returns the nullifier preimage for provided secret and index.
+/// `MAGIC_NULLIFIER + NULLIFIER_DOMAIN + NULLIFIER_VERSION + secret + uint256_be(index)`
+///
+/// The index is encoded as a fixed-width 32-byte big-endian integer, which is equivalent to
+/// `abi.encodePacked(uint256(index))` in Solidity. The preimage contains the secret, so it's
+/// zeroized on drop.
+pub fn nullifier_preimage(
+    params: &WormholeParams,
+    secret: impl AsRef<[u8]>,
+    index: U256,
+) -> Zeroizing<Vec<u8>> {
+    let secret = secret.as_ref();
+    let mut preimage =
+        Zeroizing::new(Vec::with_capacity(1 + NULLIFIER_DOMAIN.len() + 1 + secret.len() + 32));
+    preimage.push(params.magic_nullifier);
+    preimage.extend_from_slice(NULLIFIER_DOMAIN);
+    preimage.push(NULLIFIER_VERSION);
+    preimage.extend_from_slice(secret);
+    preimage.extend_from_slice(&index.to_be_bytes::<32>());
+    preimage
+}
+
+/// Returns the nullifier hash for provided secret and index.
+/// `sha256(MAGIC_NULLIFIER + NULLIFIER_DOMAIN + NULLIFIER_VERSION + secret + uint256_be(index))`
+#[inline]
+pub fn wormhole_nullifier(params: &WormholeParams, secret: impl AsRef<[u8]>, index: U256) -> B256 {
+    sha256(nullifier_preimage(params, secret, index))
 }
 
 /// Returns Proof-of-Work hash for provided secret.
 /// `sha256(MAGIC_POW + secret)`
 #[inline]
-pub fn proof_of_work_secret_hash(secret: impl AsRef<[u8]>) -> B256 {
-    sha256([&[MAGIC_POW], secret.as_ref()].concat())
+pub fn proof_of_work_secret_hash(params: &WormholeParams, secret: impl AsRef<[u8]>) -> B256 {
+    sha256(Zeroizing::new([&[params.magic_pow], secret.as_ref()].concat()))
 }
 
 /// Returns `true` if the provided Wormhole secret is valid.
-/// `sha256(MAGIC_POW + secret) % 2**POW_LOG_DIFFICULTY == 0`
+/// `len(secret) == SECRET_LENGTH && sha256(MAGIC_POW + secret) % 2**POW_LOG_DIFFICULTY == 0`
 #[inline]
-pub fn is_valid_wormhole_secret(secret: impl AsRef<[u8]>) -> bool {
-    let pow_hash = proof_of_work_secret_hash(secret);
-    U256::from_be_bytes(*pow_hash).rem(POW_DIFFICULTY_U256).is_zero()
+pub fn is_valid_wormhole_secret(params: &WormholeParams, secret: impl AsRef<[u8]>) -> bool {
+    let secret = secret.as_ref();
+    if secret.len() != SECRET_LENGTH {
+        return false;
+    }
+    let pow_hash = proof_of_work_secret_hash(params, secret);
+    U256::from_be_bytes(*pow_hash).rem(params.pow_difficulty()).is_zero()
 }
 
 #[inline]
@@ -106,12 +277,15 @@ fn sha256(data: impl AsRef<[u8]>) -> B256 {
 
 /// A valid Wormhole secret that can be used for testing.
 #[cfg(any(test, feature = "test-utils"))]
-pub const TEST_SECRET: WormholeSecret =
-    WormholeSecret(Bytes::from_static(&[0x00, 0x00, 0x00, 0x00, 0x01, 0x30, 0x5d, 0xc6]));
+pub const TEST_SECRET: WormholeSecret = WormholeSecret(Bytes::from_static(&[
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1e, 0xe8, 0x04,
+]));
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::{format, string::ToString, vec};
     use alloy_primitives::bytes::{BufMut, BytesMut};
 
     #[test]
@@ -119,16 +293,95 @@ mod tests {
         assert!(TEST_SECRET.is_valid());
     }
 
+    #[test]
+    fn redacted_secret() {
+        assert_eq!(format!("{TEST_SECRET:?}"), "WormholeSecret(\"<redacted>\")");
+        assert_eq!(TEST_SECRET.to_string(), "<redacted>");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_secret() {
+        #[derive(serde::Serialize, serde::Deserialize)]
+        struct Wrapper(#[serde(with = "serde_secret")] WormholeSecret);
+        let json = serde_json::to_string(&Wrapper(TEST_SECRET)).unwrap();
+        assert_eq!(json, "\"0x00000000000000000000000000000000000000000000000000000000001ee804\"");
+        assert_eq!(serde_json::from_str::<Wrapper>(&json).unwrap().0, TEST_SECRET);
+    }
+
+    #[test]
+    fn pow_difficulty_param() {
+        let secret = WormholeSecret::new_unchecked(Bytes::from_static(&[0x1; SECRET_LENGTH]));
+        assert!(!secret.is_valid());
+        assert!(secret.is_valid_with_params(&WormholeParams::DEFAULT.with_pow_log_difficulty(0)));
+
+        let params = WormholeParams::DEFAULT.with_pow_log_difficulty(8);
+        let secret = WormholeSecret::try_random_with_params(&params).unwrap();
+        assert!(secret.is_valid_with_params(&params));
+    }
+
+    #[test]
+    fn secret_length() {
+        let params = WormholeParams::DEFAULT.with_pow_log_difficulty(0);
+        for length in [0, 8, SECRET_LENGTH - 1, SECRET_LENGTH + 1] {
+            let secret = WormholeSecret::new_unchecked(vec![0x1; length].into());
+            assert!(!secret.has_valid_length());
+            assert!(!secret.is_valid_with_params(&params));
+        }
+        assert!(TEST_SECRET.has_valid_length());
+        assert!(
+            WormholeSecret::try_from(Bytes::from_static(&[0x00, 0x01, 0x30, 0x5d, 0xc6])).is_err()
+        );
+    }
+
+    #[test]
+    fn encrypt_cumulative_withdrawn_amount() {
+        let amount = U256::from(1_000_000);
+        let nullifier = TEST_SECRET.nullifier(U256::ZERO);
+        let encrypted = TEST_SECRET.encrypt_cumulative_withdrawn_amount(nullifier, amount);
+        assert_ne!(encrypted, B256::new(amount.to_be_bytes()));
+        assert_eq!(TEST_SECRET.decrypt_cumulative_withdrawn_amount(nullifier, encrypted), amount);
+
+        let next_nullifier = TEST_SECRET.nullifier(U256::from(1));
+        assert_ne!(
+            TEST_SECRET.encrypt_cumulative_withdrawn_amount(next_nullifier, amount),
+            encrypted
+        );
+        assert_ne!(
+            TEST_SECRET.decrypt_cumulative_withdrawn_amount(next_nullifier, encrypted),
+            amount
+        );
+    }
+
+    #[test]
+    fn nullifier_test_vectors() {
+        let vectors: serde_json::Value =
+            serde_json::from_str(include_str!("../testdata/nullifier_v1.json")).unwrap();
+        for vector in vectors.as_array().unwrap() {
+            let field = |name: &str| vector[name].as_str().unwrap();
+            let secret = WormholeSecret::new_unchecked(field("secret").parse().unwrap());
+            let index: U256 = field("index").parse().unwrap();
+            let preimage: Bytes = field("preimage").parse().unwrap();
+            let nullifier: B256 = field("nullifier").parse().unwrap();
+
+            assert_eq!(
+                *nullifier_preimage(&WormholeParams::DEFAULT, &secret.0, index),
+                preimage[..]
+            );
+            assert_eq!(secret.nullifier(index), nullifier);
+        }
+    }
+
     #[cfg(feature = "std")]
     #[test]
     fn find_valid_secret() {
         let started_at = std::time::Instant::now();
         for i in 0..u64::MAX {
-            let mut bytes = BytesMut::new();
+            let mut bytes = BytesMut::zeroed(SECRET_LENGTH - 8);
             bytes.put_u64(i);
 
             let secret = bytes.freeze();
-            if is_valid_wormhole_secret(&secret) {
+            if is_valid_wormhole_secret(&WormholeParams::DEFAULT, &secret) {
                 println!("found secret {i} in {:?}", started_at.elapsed());
                 println!("secret {:?}", alloy_primitives::hex::encode(&secret[..]));
                 break;
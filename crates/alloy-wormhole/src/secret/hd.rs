@@ -0,0 +1,147 @@
+//! Deterministic derivation of [`WormholeSecret`]s from a master seed.
+
+use super::{is_valid_wormhole_secret, sha256, WormholeSecret};
+use crate::{WormholeParams, HD_MASTER_DOMAIN, HD_SECRET_DOMAIN};
+use alloy_primitives::B256;
+use core::fmt;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+/// The master seed for deterministic derivation of [`WormholeSecret`]s.
+///
+/// The secret for each account index is the first candidate in the per-index sequence that
+/// satisfies the Proof-of-Work condition:
+/// ```text
+/// candidate(index, nonce) = sha256(HD_SECRET_DOMAIN + seed + uint32_be(index) + uint64_be(nonce))
+/// ```
+///
+/// This allows recovering all burn addresses from a single backup of the seed. The seed is zeroized
+/// on drop.
+#[derive(Clone, PartialEq, Eq)]
+pub struct WormholeMasterSeed(B256);
+
+impl fmt::Debug for WormholeMasterSeed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("WormholeMasterSeed").field(&"<redacted>").finish()
+    }
+}
+
+impl Drop for WormholeMasterSeed {
+    fn drop(&mut self) {
+        self.0.as_mut_slice().zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for WormholeMasterSeed {}
+
+impl From<B256> for WormholeMasterSeed {
+    fn from(seed: B256) -> Self {
+        Self::new(seed)
+    }
+}
+
+impl WormholeMasterSeed {
+    /// Creates new [`WormholeMasterSeed`] from 32-byte seed.
+    pub const fn new(seed: B256) -> Self {
+        Self(seed)
+    }
+
+    /// Creates new [`WormholeMasterSeed`] from the BIP-39 seed.
+    /// `sha256(HD_MASTER_DOMAIN + bip39_seed)`
+    pub fn from_bip39_seed(bip39_seed: &[u8; 64]) -> Self {
+        Self(sha256(Zeroizing::new([HD_MASTER_DOMAIN, &bip39_seed[..]].concat())))
+    }
+
+    /// Creates new [`WormholeMasterSeed`] from the BIP-39 mnemonic and passphrase.
+    ///
+    /// NOTE: The mnemonic and passphrase are expected to be in UTF-8 NFKD normalized form,
+    /// which is the case for the English wordlist and ASCII passphrases.
+    #[cfg(feature = "bip39")]
+    pub fn from_mnemonic(mnemonic: &str, passphrase: &str) -> Result<Self, bip39::Error> {
+        let mnemonic = bip39::Mnemonic::parse_normalized(mnemonic)?;
+        let bip39_seed = Zeroizing::new(mnemonic.to_seed_normalized(passphrase));
+        Ok(Self::from_bip39_seed(&bip39_seed))
+    }
+
+    /// Returns the secret candidate for provided account index and nonce.
+    /// `sha256(HD_SECRET_DOMAIN + seed + uint32_be(index) + uint64_be(nonce))`
+    pub fn candidate(&self, index: u32, nonce: u64) -> B256 {
+        sha256(Zeroizing::new(
+            [HD_SECRET_DOMAIN, self.0.as_slice(), &index.to_be_bytes(), &nonce.to_be_bytes()]
+                .concat(),
+        ))
+    }
+
+    /// Derives the **valid** [`WormholeSecret`] for provided account index.
+    pub fn derive_secret(&self, index: u32) -> WormholeSecret {
+        self.derive_secret_with_params(&WormholeParams::DEFAULT, index)
+    }
+
+    /// Derives the [`WormholeSecret`] for provided account index that is **valid** under provided
+    /// parameters.
+    pub fn derive_secret_with_params(&self, params: &WormholeParams, index: u32) -> WormholeSecret {
+        let mut nonce = 0;
+        loop {
+            let candidate = self.candidate(index, nonce);
+            if is_valid_wormhole_secret(params, candidate) {
+                return WormholeSecret::new_unchecked(candidate.into());
+            }
+            nonce += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::b256;
+
+    #[test]
+    fn derive_secret() {
+        let params = WormholeParams::DEFAULT.with_pow_log_difficulty(8);
+        let seed = WormholeMasterSeed::new(B256::ZERO);
+        let secret = seed.derive_secret_with_params(&params, 0);
+        assert!(secret.is_valid_with_params(&params));
+        assert_eq!(
+            secret.as_ref()[..],
+            b256!("0xb6a292887a995e9d9acda90a41f19968f838ce651c38a67766c01196e1358436")[..]
+        );
+        assert_eq!(seed.derive_secret_with_params(&params, 0), secret);
+        assert_ne!(seed.derive_secret_with_params(&params, 1), secret);
+    }
+
+    #[test]
+    fn from_bip39_seed() {
+        // BIP-39 seed of "abandon ... about" mnemonic with "TREZOR" passphrase.
+        let bip39_seed = alloy_primitives::hex!("c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04");
+        let seed = WormholeMasterSeed::from_bip39_seed(&bip39_seed);
+        assert_eq!(
+            seed.0,
+            b256!("0x7ce074022e52af533d907fbc575342af190b85fc9c29bb4e652dbe005ad69639")
+        );
+
+        let params = WormholeParams::DEFAULT.with_pow_log_difficulty(8);
+        for (index, expected) in [
+            b256!("0xa14665e640c333ae9eeed82b833bb86bb09faf57ac0f505eb39f0d211a7f64f8"),
+            b256!("0x9a273dd3ecf6d0e0047ac32e7d7a1326f0eb2e6035ab176ab5e5ed7ff31ab769"),
+            b256!("0x8910eb0fe7a6f7a0d59931654f9a71270e441c89f3b6223bb3467ca5748dd07e"),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let secret = seed.derive_secret_with_params(&params, index as u32);
+            assert_eq!(secret.as_ref()[..], expected[..]);
+        }
+    }
+
+    #[cfg(feature = "bip39")]
+    #[test]
+    fn from_mnemonic() {
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon \
+                        abandon abandon about";
+        assert_eq!(
+            WormholeMasterSeed::from_mnemonic(mnemonic, "TREZOR").unwrap().0,
+            b256!("0x7ce074022e52af533d907fbc575342af190b85fc9c29bb4e652dbe005ad69639")
+        );
+        assert!(WormholeMasterSeed::from_mnemonic("abandon", "").is_err());
+    }
+}
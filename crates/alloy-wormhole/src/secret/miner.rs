@@ -0,0 +1,219 @@
+//! Parallel [`WormholeSecret`] mining.
+
+use super::{is_valid_wormhole_secret, next_secret_candidate, WormholeSecret};
+use crate::WormholeParams;
+use std::{
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, OnceLock,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+use zeroize::Zeroizing;
+
+/// The number of attempts after which the worker reports progress and checks for cancellation.
+const ATTEMPTS_PER_BATCH: u64 = 1024;
+
+/// The multi-threaded miner of **valid** [`WormholeSecret`]s.
+///
+/// Each worker thread draws a single random seed and derives candidates by incrementing the counter
+/// in the last 8 bytes of the seed, avoiding a syscall per attempt.
+#[derive(Clone, Debug)]
+pub struct WormholeSecretMiner {
+    params: WormholeParams,
+    threads: NonZeroUsize,
+    progress_interval: Duration,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl WormholeSecretMiner {
+    /// Creates new [`WormholeSecretMiner`] for provided parameters.
+    /// Uses all available threads and reports progress every second by default.
+    pub fn new(params: WormholeParams) -> Self {
+        Self {
+            params,
+            threads: thread::available_parallelism().unwrap_or(NonZeroUsize::MIN),
+            progress_interval: Duration::from_secs(1),
+            cancelled: Arc::default(),
+        }
+    }
+
+    /// Sets the number of worker threads.
+    pub const fn with_threads(mut self, threads: NonZeroUsize) -> Self {
+        self.threads = threads;
+        self
+    }
+
+    /// Sets the interval between progress reports.
+    pub const fn with_progress_interval(mut self, progress_interval: Duration) -> Self {
+        self.progress_interval = progress_interval;
+        self
+    }
+
+    /// Returns the handle for cancelling the mining from another thread.
+    pub fn cancellation_handle(&self) -> MiningCancellationHandle {
+        MiningCancellationHandle(self.cancelled.clone())
+    }
+
+    /// Mines for a new **valid** [`WormholeSecret`].
+    ///
+    /// Blocks until the secret is found or the mining is cancelled, calling `on_progress`
+    /// from the current thread every progress interval.
+    ///
+    /// Returns `Ok(None)` if the mining was cancelled.
+    ///
+    /// # Errors
+    ///
+    /// This function only propagates the error from the underlying call to
+    /// [`getrandom`](getrandom::getrandom).
+    pub fn mine<F>(&self, mut on_progress: F) -> Result<Option<WormholeSecret>, getrandom::Error>
+    where
+        F: FnMut(&MiningProgress),
+    {
+        let seeds = (0..self.threads.get())
+            .map(|_| {
+                let mut seed = Zeroizing::new([0u8; 32]);
+                getrandom::getrandom(seed.as_mut_slice())?;
+                Ok(seed)
+            })
+            .collect::<Result<Vec<_>, getrandom::Error>>()?;
+
+        let started_at = Instant::now();
+        let attempts = AtomicU64::new(0);
+        let found = OnceLock::new();
+        let main_thread = thread::current();
+        let is_done = || found.get().is_some() || self.cancelled.load(Ordering::Relaxed);
+
+        thread::scope(|scope| {
+            for mut candidate in seeds {
+                let (attempts, found, main_thread, is_done) =
+                    (&attempts, &found, &main_thread, &is_done);
+                scope.spawn(move || {
+                    while !is_done() {
+                        for _ in 0..ATTEMPTS_PER_BATCH {
+                            if is_valid_wormhole_secret(&self.params, candidate.as_slice()) {
+                                let _ = found
+                                    .set(WormholeSecret::new_unchecked(candidate.to_vec().into()));
+                                main_thread.unpark();
+                                return;
+                            }
+                            next_secret_candidate(candidate.as_mut_slice());
+                        }
+                        attempts.fetch_add(ATTEMPTS_PER_BATCH, Ordering::Relaxed);
+                    }
+                });
+            }
+
+            let mut next_report_at = started_at + self.progress_interval;
+            while !is_done() {
+                thread::park_timeout(next_report_at.saturating_duration_since(Instant::now()));
+                if Instant::now() >= next_report_at && !is_done() {
+                    on_progress(&MiningProgress {
+                        attempts: attempts.load(Ordering::Relaxed),
+                        elapsed: started_at.elapsed(),
+                        pow_log_difficulty: self.params.pow_log_difficulty,
+                    });
+                    next_report_at += self.progress_interval;
+                }
+            }
+        });
+
+        Ok(found.into_inner())
+    }
+}
+
+/// The handle for cancelling the [`WormholeSecretMiner::mine`].
+#[derive(Clone, Debug)]
+pub struct MiningCancellationHandle(Arc<AtomicBool>);
+
+impl MiningCancellationHandle {
+    /// Cancels the mining.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns `true` if the mining has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// The progress of the [`WormholeSecretMiner::mine`].
+#[derive(Clone, Copy, Debug)]
+pub struct MiningProgress {
+    /// The number of attempted candidates.
+    pub attempts: u64,
+    /// The time elapsed since the start of mining.
+    pub elapsed: Duration,
+    /// The exponent for Proof-of-Work condition on the secret.
+    pub pow_log_difficulty: u8,
+}
+
+impl MiningProgress {
+    /// Returns the number of attempted candidates per second.
+    pub fn hash_rate(&self) -> f64 {
+        self.attempts as f64 / self.elapsed.as_secs_f64()
+    }
+
+    /// Returns the expected number of attempts to find a valid secret.
+    /// `2**POW_LOG_DIFFICULTY`
+    pub fn expected_attempts(&self) -> f64 {
+        2f64.powi(self.pow_log_difficulty as i32)
+    }
+
+    /// Returns the expected time until a valid secret is found at the current hash rate.
+    ///
+    /// Since every attempt is independent, the expected time does not depend on the number of
+    /// attempts made so far. Returns `None` if the hash rate is zero or the time is too large to
+    /// be represented.
+    pub fn eta(&self) -> Option<Duration> {
+        Duration::try_from_secs_f64(self.expected_attempts() / self.hash_rate()).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mine_secret() {
+        let params = WormholeParams::DEFAULT.with_pow_log_difficulty(8);
+        let miner = WormholeSecretMiner::new(params)
+            .with_threads(NonZeroUsize::new(2).unwrap())
+            .with_progress_interval(Duration::from_millis(1));
+        let secret = miner.mine(|_| {}).unwrap().unwrap();
+        assert!(secret.is_valid_with_params(&params));
+    }
+
+    #[test]
+    fn cancel_mining() {
+        let miner = WormholeSecretMiner::new(WormholeParams::DEFAULT.with_pow_log_difficulty(255))
+            .with_progress_interval(Duration::from_millis(1));
+        let handle = miner.cancellation_handle();
+        let mut reports = 0;
+        let secret = miner
+            .mine(|_| {
+                reports += 1;
+                if reports == 3 {
+                    handle.cancel();
+                }
+            })
+            .unwrap();
+        assert_eq!(secret, None);
+        assert_eq!(reports, 3);
+        assert!(miner.cancellation_handle().is_cancelled());
+    }
+
+    #[test]
+    fn progress_eta() {
+        let mut progress =
+            MiningProgress { attempts: 0, elapsed: Duration::from_secs(1), pow_log_difficulty: 24 };
+        assert_eq!(progress.eta(), None);
+
+        progress.attempts = 1 << 20;
+        assert_eq!(progress.hash_rate(), (1 << 20) as f64);
+        assert_eq!(progress.eta(), Some(Duration::from_secs(16)));
+    }
+}
@@ -0,0 +1,375 @@
+//! Stateful validation of the [`WormholeTx`].
+
+use crate::{WormholeParams, WormholeTx, WormholeTxError, WormholeTxProof};
+use alloy_primitives::{Address, B256};
+use core::fmt;
+
+/// The source of chain state required for [`WormholeTx`] validation.
+pub trait WormholeStateProvider {
+    /// The error returned by the provider.
+    type Error;
+
+    /// Returns the address of the nullifier system contract.
+    /// `WORMHOLE_NULLIFIER_ADDRESS`
+    fn nullifier_address(&self) -> Address;
+
+    /// Returns the Wormhole protocol parameters of the chain.
+    fn params(&self) -> WormholeParams;
+
+    /// Returns the state root of the block with provided number or `None` if the block is unknown.
+    /// `get_state_root(block_number)`
+    fn state_root(&self, block_number: u64) -> Result<Option<B256>, Self::Error>;
+
+    /// Returns the value of the nullifier system contract storage slot.
+    /// `SLOAD(WORMHOLE_NULLIFIER_ADDRESS, nullifier)`
+    fn nullifier_slot(&self, nullifier: B256) -> Result<B256, Self::Error>;
+}
+
+/// The verifier of the zero-knowledge proof contained in [`WormholeTxProof`].
+pub trait WormholeTxProofVerifier {
+    /// The error returned by the verifier if proof verification fails.
+    type Error;
+
+    /// Verifies that the proof attests to the program execution with the public values contained
+    /// in the [`WormholeTxProof`].
+    fn verify_tx_proof(&self, proof: &WormholeTxProof) -> Result<(), Self::Error>;
+}
+
+/// Validates the [`WormholeTx`] against the current chain state.
+///
+/// Performs the following checks:
+/// 1. The proof recipient matches the transaction beneficiary.
+/// 2. `proof.nullifier_address == WORMHOLE_NULLIFIER_ADDRESS`
+/// 3. `proof.params == params`
+/// 4. `get_state_root(proof_block_number) == proof.state_root`
+/// 5. The proof contains at least one nullifier and all nullifiers are distinct.
+/// 6. `SLOAD(WORMHOLE_NULLIFIER_ADDRESS, nullifier) == 0` for each nullifier in the proof.
+/// 7. The proof is a valid zero-knowledge proof of the program execution.
+///
+/// The proof verification is performed last since it is the most expensive check.
+pub fn validate_wormhole_tx<P, V>(
+    tx: &WormholeTx,
+    provider: &P,
+    verifier: &V,
+) -> Result<(), WormholeTxValidationError<P::Error, V::Error>>
+where
+    P: WormholeStateProvider,
+    V: WormholeTxProofVerifier,
+{
+    tx.validate_recipient()?;
+
+    let nullifier_address = provider.nullifier_address();
+    if nullifier_address != tx.proof.nullifier_address {
+        return Err(WormholeTxValidationError::NullifierAddressMismatch {
+            expected: nullifier_address,
+            got: tx.proof.nullifier_address,
+        });
+    }
+
+    let params = provider.params();
+    if params != tx.proof.params {
+        return Err(WormholeTxValidationError::ParamsMismatch {
+            expected: params,
+            got: tx.proof.params,
+        });
+    }
+
+    let state_root = provider
+        .state_root(tx.proof_block_number)
+        .map_err(WormholeTxValidationError::Provider)?
+        .ok_or(WormholeTxValidationError::UnknownBlock(tx.proof_block_number))?;
+    if state_root != tx.proof.state_root {
+        return Err(WormholeTxValidationError::StateRootMismatch {
+            expected: state_root,
+            got: tx.proof.state_root,
+        });
+    }
+
+    if tx.proof.nullifiers.is_empty() {
+        return Err(WormholeTxValidationError::MissingNullifiers);
+    }
+    for (index, entry) in tx.proof.nullifiers.iter().enumerate() {
+        if tx.proof.nullifiers[..index].iter().any(|other| other.nullifier == entry.nullifier) {
+            return Err(WormholeTxValidationError::DuplicateNullifier(entry.nullifier));
+        }
+
+        let nullifier_slot = provider
+            .nullifier_slot(entry.nullifier)
+            .map_err(WormholeTxValidationError::Provider)?;
+        if !nullifier_slot.is_zero() {
+            return Err(WormholeTxValidationError::NullifierSpent(entry.nullifier));
+        }
+    }
+
+    verifier.verify_tx_proof(&tx.proof).map_err(WormholeTxValidationError::InvalidProof)?;
+
+    Ok(())
+}
+
+/// The error returned by [`validate_wormhole_tx`].
+#[derive(PartialEq, Eq, Debug)]
+pub enum WormholeTxValidationError<P, V> {
+    /// The transaction is inconsistent with its proof.
+    Tx(WormholeTxError),
+    /// The proof nullifier address is not the nullifier system contract address.
+    NullifierAddressMismatch {
+        /// The nullifier system contract address.
+        expected: Address,
+        /// The nullifier address committed to by the proof.
+        got: Address,
+    },
+    /// The proof parameters do not match the chain parameters.
+    ParamsMismatch {
+        /// The chain parameters.
+        expected: WormholeParams,
+        /// The parameters committed to by the proof.
+        got: WormholeParams,
+    },
+    /// The block of the proof state root is unknown.
+    UnknownBlock(u64),
+    /// The proof state root does not match the state root of the block.
+    StateRootMismatch {
+        /// The state root of the block.
+        expected: B256,
+        /// The state root committed to by the proof.
+        got: B256,
+    },
+    /// The proof does not contain any nullifiers.
+    MissingNullifiers,
+    /// The proof contains the same nullifier more than once.
+    DuplicateNullifier(B256),
+    /// The nullifier has already been used.
+    NullifierSpent(B256),
+    /// The zero-knowledge proof verification failed.
+    InvalidProof(V),
+    /// The state provider failed.
+    Provider(P),
+}
+
+impl<P, V> core::error::Error for WormholeTxValidationError<P, V>
+where
+    P: fmt::Debug + fmt::Display,
+    V: fmt::Debug + fmt::Display,
+{
+}
+
+impl<P: fmt::Display, V: fmt::Display> fmt::Display for WormholeTxValidationError<P, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tx(error) => write!(f, "{error}"),
+            Self::NullifierAddressMismatch { expected, got } => {
+                write!(f, "nullifier address mismatch: expected {expected}, got {got}")
+            }
+            Self::ParamsMismatch { expected, got } => {
+                write!(f, "params mismatch: expected {expected:?}, got {got:?}")
+            }
+            Self::UnknownBlock(number) => write!(f, "unknown block {number}"),
+            Self::StateRootMismatch { expected, got } => {
+                write!(f, "state root mismatch: expected {expected}, got {got}")
+            }
+            Self::MissingNullifiers => write!(f, "missing nullifiers"),
+            Self::DuplicateNullifier(nullifier) => write!(f, "duplicate nullifier {nullifier}"),
+            Self::NullifierSpent(nullifier) => write!(f, "nullifier {nullifier} already spent"),
+            Self::InvalidProof(error) => write!(f, "invalid proof: {error}"),
+            Self::Provider(error) => write!(f, "provider: {error}"),
+        }
+    }
+}
+
+impl<P, V> From<WormholeTxError> for WormholeTxValidationError<P, V> {
+    fn from(error: WormholeTxError) -> Self {
+        Self::Tx(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::WormholeNullifierOutput;
+    use alloc::vec;
+    use alloy_primitives::{address, b256, U256};
+
+    const NULLIFIER_ADDRESS: Address = address!("ce8f0b46cc1527f27429938d3cc85bf7d270a8f6");
+
+    const STATE_ROOT: B256 =
+        b256!("0x153a3b2082ce10f2c9e421ac684d1d27a96af410000bf94bb986ed227d566cf0");
+    const SPENT_NULLIFIER: B256 =
+        b256!("0xb3f99dab37ecdef88863af5231ae2b72faa95793ff88ed07de9c4e58315f6447");
+
+    struct MockProvider;
+
+    impl WormholeStateProvider for MockProvider {
+        type Error = &'static str;
+
+        fn nullifier_address(&self) -> Address {
+            NULLIFIER_ADDRESS
+        }
+
+        fn params(&self) -> WormholeParams {
+            WormholeParams::DEFAULT
+        }
+
+        fn state_root(&self, block_number: u64) -> Result<Option<B256>, Self::Error> {
+            match block_number {
+                0 => Err("provider error"),
+                1 => Ok(Some(STATE_ROOT)),
+                _ => Ok(None),
+            }
+        }
+
+        fn nullifier_slot(&self, nullifier: B256) -> Result<B256, Self::Error> {
+            Ok(if nullifier == SPENT_NULLIFIER { B256::with_last_byte(1) } else { B256::ZERO })
+        }
+    }
+
+    struct MockVerifier;
+
+    impl WormholeTxProofVerifier for MockVerifier {
+        type Error = &'static str;
+
+        fn verify_tx_proof(&self, proof: &WormholeTxProof) -> Result<(), Self::Error> {
+            if proof.proof.is_empty() {
+                Err("empty proof")
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    fn valid_tx() -> WormholeTx {
+        let recipient = Address::with_last_byte(1);
+        WormholeTx {
+            to: recipient,
+            proof_block_number: 1,
+            proof: WormholeTxProof {
+                state_root: STATE_ROOT,
+                nullifiers: vec![WormholeNullifierOutput {
+                    nullifier: B256::with_last_byte(1),
+                    ..Default::default()
+                }],
+                nullifier_address: NULLIFIER_ADDRESS,
+                withdraw_value: U256::from(1),
+                recipient,
+                proof: [0x1].into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn validate_tx() {
+        assert_eq!(validate_wormhole_tx(&valid_tx(), &MockProvider, &MockVerifier), Ok(()));
+    }
+
+    #[test]
+    fn recipient_mismatch() {
+        let mut tx = valid_tx();
+        tx.to = Address::with_last_byte(2);
+        assert_eq!(
+            validate_wormhole_tx(&tx, &MockProvider, &MockVerifier),
+            Err(WormholeTxValidationError::Tx(WormholeTxError::RecipientMismatch {
+                expected: tx.to,
+                got: tx.proof.recipient,
+            }))
+        );
+    }
+
+    #[test]
+    fn nullifier_address_mismatch() {
+        let mut tx = valid_tx();
+        tx.proof.nullifier_address = Address::ZERO;
+        assert_eq!(
+            validate_wormhole_tx(&tx, &MockProvider, &MockVerifier),
+            Err(WormholeTxValidationError::NullifierAddressMismatch {
+                expected: NULLIFIER_ADDRESS,
+                got: Address::ZERO,
+            })
+        );
+    }
+
+    #[test]
+    fn params_mismatch() {
+        let mut tx = valid_tx();
+        tx.proof.params = WormholeParams::DEFAULT.with_pow_log_difficulty(8);
+        assert_eq!(
+            validate_wormhole_tx(&tx, &MockProvider, &MockVerifier),
+            Err(WormholeTxValidationError::ParamsMismatch {
+                expected: WormholeParams::DEFAULT,
+                got: tx.proof.params,
+            })
+        );
+    }
+
+    #[test]
+    fn invalid_state_root() {
+        let mut tx = valid_tx();
+        tx.proof_block_number = 0;
+        assert_eq!(
+            validate_wormhole_tx(&tx, &MockProvider, &MockVerifier),
+            Err(WormholeTxValidationError::Provider("provider error"))
+        );
+
+        tx.proof_block_number = 2;
+        assert_eq!(
+            validate_wormhole_tx(&tx, &MockProvider, &MockVerifier),
+            Err(WormholeTxValidationError::UnknownBlock(2))
+        );
+
+        tx.proof_block_number = 1;
+        tx.proof.state_root = B256::ZERO;
+        assert_eq!(
+            validate_wormhole_tx(&tx, &MockProvider, &MockVerifier),
+            Err(WormholeTxValidationError::StateRootMismatch {
+                expected: STATE_ROOT,
+                got: B256::ZERO
+            })
+        );
+    }
+
+    #[test]
+    fn nullifier_spent() {
+        let mut tx = valid_tx();
+        tx.proof.nullifiers[0].nullifier = SPENT_NULLIFIER;
+        assert_eq!(
+            validate_wormhole_tx(&tx, &MockProvider, &MockVerifier),
+            Err(WormholeTxValidationError::NullifierSpent(SPENT_NULLIFIER))
+        );
+
+        let mut tx = valid_tx();
+        tx.proof
+            .nullifiers
+            .push(WormholeNullifierOutput { nullifier: SPENT_NULLIFIER, ..Default::default() });
+        assert_eq!(
+            validate_wormhole_tx(&tx, &MockProvider, &MockVerifier),
+            Err(WormholeTxValidationError::NullifierSpent(SPENT_NULLIFIER))
+        );
+    }
+
+    #[test]
+    fn invalid_nullifiers() {
+        let mut tx = valid_tx();
+        tx.proof.nullifiers.clear();
+        assert_eq!(
+            validate_wormhole_tx(&tx, &MockProvider, &MockVerifier),
+            Err(WormholeTxValidationError::MissingNullifiers)
+        );
+
+        let mut tx = valid_tx();
+        tx.proof.nullifiers.push(tx.proof.nullifiers[0]);
+        assert_eq!(
+            validate_wormhole_tx(&tx, &MockProvider, &MockVerifier),
+            Err(WormholeTxValidationError::DuplicateNullifier(B256::with_last_byte(1)))
+        );
+    }
+
+    #[test]
+    fn invalid_proof() {
+        let mut tx = valid_tx();
+        tx.proof.proof = Default::default();
+        assert_eq!(
+            validate_wormhole_tx(&tx, &MockProvider, &MockVerifier),
+            Err(WormholeTxValidationError::InvalidProof("empty proof"))
+        );
+    }
+}
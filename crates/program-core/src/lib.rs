@@ -9,7 +9,11 @@ use alloy_trie::{
     proof::{verify_proof, ProofVerificationError},
     Nibbles, TrieAccount,
 };
-use alloy_wormhole::WormholeSecret;
+use alloy_wormhole::{nullifier::cumulative_withdrawn_amount_hash, WormholeParams, WormholeSecret};
+
+pub use alloy_wormhole::{
+    WormholeAggregationOutput, WormholeNullifierOutput, WormholeProgramOutput,
+};
 use core::fmt;
 
 /// Executes the Wormhole withdrawal verification program.
@@ -18,13 +22,15 @@ use core::fmt;
 /// amount in a privacy-preserving manner. It performs several cryptographic and
 /// state-based checks to ensure the withdrawal is legitimate:
 ///
-/// 1. Validates the secret used to generate nullifiers.
+/// 1. Validates the length and Proof-of-Work of the secret used to generate nullifiers.
 /// 2. Verifies the correctness of the withdrawal amount against deposit and previously withdrawn
-///    amounts.
-/// 3. Checks consistency of withdrawal index and related storage proof input.
-/// 4. Validates Merkle-Patricia Trie proofs for:
-///     - The deposit account state,
+///    amounts. The withdrawable total is capped at the max deposit.
+/// 3. Checks that the withdrawal recipient is set and the relayer fee does not exceed the
+///    withdrawal amount.
+/// 4. Checks consistency of withdrawal index and related storage proof input.
+/// 5. Validates Merkle-Patricia Trie proofs for:
 ///     - The Wormhole nullifier account,
+///     - The deposit account state,
 ///     - The previous withdrawal's nullifier inclusion in storage (if applicable).
 ///
 /// Upon successful validation, it computes the current nullifier for this withdrawal
@@ -42,50 +48,126 @@ use core::fmt;
 pub fn execute_wormhole_program(
     input: WormholeProgramInput,
 ) -> Result<WormholeProgramOutput, WormholeProgramError> {
-    // Validate the input.
-    if !input.secret.is_valid() {
-        return Err(WormholeProgramError::InvalidSecret);
+    execute_wormhole_program_with_observer(input, &mut ())
+}
+
+/// Executes the Wormhole withdrawal verification program and notifies the observer about the
+/// start and the end of each [`WormholeProgramStep`].
+///
+/// See [`execute_wormhole_program`] for more details.
+pub fn execute_wormhole_program_with_observer<O: WormholeProgramObserver>(
+    input: WormholeProgramInput,
+    observer: &mut O,
+) -> Result<WormholeProgramOutput, WormholeProgramError> {
+    execute_wormhole_batch_program_with_observer(input.into(), observer)
+}
+
+/// Executes the Wormhole withdrawal verification program for the batch of withdrawals from
+/// multiple burn addresses against the same state root.
+///
+/// Each withdrawal is validated the same way as in [`execute_wormhole_program`]. The relayer fee
+/// is validated against the total withdraw amount. The nullifiers of all withdrawals must be
+/// distinct.
+///
+/// The output contains the total withdraw amount and the nullifiers of the withdrawals in the
+/// order of the input.
+pub fn execute_wormhole_batch_program(
+    input: WormholeBatchProgramInput,
+) -> Result<WormholeProgramOutput, WormholeProgramError> {
+    execute_wormhole_batch_program_with_observer(input, &mut ())
+}
+
+/// Executes the Wormhole withdrawal verification program for the batch of withdrawals and
+/// notifies the observer about the start and the end of each [`WormholeProgramStep`].
+///
+/// See [`execute_wormhole_batch_program`] for more details.
+pub fn execute_wormhole_batch_program_with_observer<O: WormholeProgramObserver>(
+    input: WormholeBatchProgramInput,
+    observer: &mut O,
+) -> Result<WormholeProgramOutput, WormholeProgramError> {
+    if input.withdrawals.is_empty() {
+        return Err(WormholeProgramError::EmptyBatch);
+    }
+
+    let mut withdraw_amount = U256::ZERO;
+    let mut next_cumulative_withdrawn_amounts = Vec::with_capacity(input.withdrawals.len());
+    for withdrawal in &input.withdrawals {
+        // Validate the secret.
+        observer.on_step_start(WormholeProgramStep::ValidateSecret);
+        if !withdrawal.secret.has_valid_length() {
+            return Err(WormholeProgramError::InvalidSecretLength);
+        }
+        if !withdrawal.secret.is_valid_with_params(&input.params) {
+            return Err(WormholeProgramError::InvalidSecret);
+        }
+        observer.on_step_end(WormholeProgramStep::ValidateSecret);
+
+        // Validate withdraw amount
+        observer.on_step_start(WormholeProgramStep::ValidateWithdrawAmount);
+        if withdrawal.withdraw_amount.is_zero() {
+            return Err(WormholeProgramError::InvalidWithdrawAmount);
+        }
+        let next_cumulative_withdrawn_amount = withdrawal
+            .withdraw_amount
+            .checked_add(withdrawal.cumulative_withdrawn_amount)
+            .ok_or(WormholeProgramError::InvalidWithdrawAmount)?;
+        if next_cumulative_withdrawn_amount > withdrawal.deposit_amount {
+            return Err(WormholeProgramError::InvalidWithdrawAmount);
+        }
+        if next_cumulative_withdrawn_amount >
+            input.params.max_withdrawable_amount(withdrawal.deposit_amount)
+        {
+            return Err(WormholeProgramError::MaxDepositExceeded);
+        }
+        withdraw_amount = withdraw_amount
+            .checked_add(withdrawal.withdraw_amount)
+            .ok_or(WormholeProgramError::InvalidWithdrawAmount)?;
+        next_cumulative_withdrawn_amounts.push(next_cumulative_withdrawn_amount);
+        observer.on_step_end(WormholeProgramStep::ValidateWithdrawAmount);
     }
 
-    // Validate withdraw amount
-    if input.withdraw_amount.is_zero() {
-        return Err(WormholeProgramError::InvalidWithdrawAmount);
+    // Validate the withdrawal recipient.
+    observer.on_step_start(WormholeProgramStep::ValidateRecipient);
+    if input.recipient.is_zero() {
+        return Err(WormholeProgramError::InvalidRecipient);
     }
-    let next_cumulative_withdrawn_amount = input
-        .withdraw_amount
-        .checked_add(input.cumulative_withdrawn_amount)
-        .ok_or(WormholeProgramError::InvalidWithdrawAmount)?;
-    if next_cumulative_withdrawn_amount > input.deposit_amount {
-        return Err(WormholeProgramError::InvalidWithdrawAmount);
+    observer.on_step_end(WormholeProgramStep::ValidateRecipient);
+
+    // Validate the relayer fee. The fee is deducted from the total withdraw amount and the
+    // remainder is minted to the recipient.
+    observer.on_step_start(WormholeProgramStep::ValidateRelayerFee);
+    if input.relayer.is_zero() && !input.relayer_fee.is_zero() {
+        return Err(WormholeProgramError::InvalidRelayerFee);
     }
+    withdraw_amount
+        .checked_sub(input.relayer_fee)
+        .ok_or(WormholeProgramError::InvalidRelayerFee)?;
+    observer.on_step_end(WormholeProgramStep::ValidateRelayerFee);
 
     // Validate withdrawal index against other input fields.
-    if input.withdrawal_index.is_zero() {
-        if !input.cumulative_withdrawn_amount.is_zero() {
-            panic!("cumulative withdrawn amount must be 0 for first withdrawal")
-        }
+    for withdrawal in &input.withdrawals {
+        observer.on_step_start(WormholeProgramStep::ValidateWithdrawalIndex);
+        if withdrawal.withdrawal_index.is_zero() {
+            if !withdrawal.cumulative_withdrawn_amount.is_zero() {
+                return Err(WormholeProgramError::InconsistentFirstWithdrawal);
+            }
 
-        if !input.previous_nullifier_storage_proof.is_empty() {
-            panic!("storage proof for previous nullifier must be empty")
+            if !withdrawal.previous_nullifier_storage_proof.is_empty() {
+                return Err(WormholeProgramError::UnexpectedPreviousNullifierProof);
+            }
         }
+        observer.on_step_end(WormholeProgramStep::ValidateWithdrawalIndex);
     }
 
-    // Verify the deposit account state proof.
-    let deposit_address = input.secret.burn_address();
-    let deposit_address_nibbles = Nibbles::unpack(keccak256(deposit_address));
-    let expected =
-        alloy_rlp::encode(TrieAccount { balance: input.deposit_amount, ..Default::default() });
-    verify_proof(
-        input.state_root,
-        deposit_address_nibbles,
-        Some(expected),
-        &input.deposit_account_proof,
-    )?;
-
-    // Verify the Wormhole nullifier account state proof.
+    // Verify the Wormhole nullifier account state proof. The account is shared by all
+    // withdrawals.
+    observer.on_step_start(WormholeProgramStep::VerifyNullifierAccountProof);
     let nullifier_address_nibbles = Nibbles::unpack(keccak256(input.nullifier_address));
     let nullifier_leaf_node = {
-        let last_node_encoded = input.nullifier_account_proof.last().unwrap();
+        let last_node_encoded = input
+            .nullifier_account_proof
+            .last()
+            .ok_or(WormholeProgramError::EmptyNullifierAccountProof)?;
         let nullifier_node = TrieNode::decode(&mut &last_node_encoded[..])?;
         if let TrieNode::Leaf(leaf) = nullifier_node {
             leaf
@@ -100,42 +182,144 @@ pub fn execute_wormhole_program(
         Some(nullifier_leaf_node.value),
         &input.nullifier_account_proof,
     )?;
+    observer.on_step_end(WormholeProgramStep::VerifyNullifierAccountProof);
 
-    // Verify previous withdrawal nullifier inclusion storage proof.
-    let cumulative_withdrawn_amount_hashed =
-        keccak256(B256::new(input.cumulative_withdrawn_amount.to_be_bytes()));
-    if !input.withdrawal_index.is_zero() {
-        let previous_withdrawal_index = input.withdrawal_index - U256::from(1);
-        let previous_nullifier = input.secret.nullifier(previous_withdrawal_index);
-        let previous_nullifier_nibbles = Nibbles::unpack(keccak256(previous_nullifier));
-        let expected = alloy_rlp::encode_fixed_size(&cumulative_withdrawn_amount_hashed).to_vec();
+    let mut nullifiers = Vec::<WormholeNullifierOutput>::with_capacity(input.withdrawals.len());
+    for (withdrawal, next_cumulative_withdrawn_amount) in
+        input.withdrawals.iter().zip(next_cumulative_withdrawn_amounts)
+    {
+        // Verify the deposit account state proof.
+        observer.on_step_start(WormholeProgramStep::VerifyDepositAccountProof);
+        let deposit_address = withdrawal.secret.burn_address_with_params(&input.params);
+        let deposit_address_nibbles = Nibbles::unpack(keccak256(deposit_address));
+        let expected = alloy_rlp::encode(TrieAccount {
+            balance: withdrawal.deposit_amount,
+            ..Default::default()
+        });
         verify_proof(
-            nullifier_account.storage_root,
-            previous_nullifier_nibbles,
+            input.state_root,
+            deposit_address_nibbles,
             Some(expected),
-            &input.previous_nullifier_storage_proof,
+            &withdrawal.deposit_account_proof,
         )?;
-    }
+        observer.on_step_end(WormholeProgramStep::VerifyDepositAccountProof);
+
+        // Verify previous withdrawal nullifier inclusion storage proof.
+        observer.on_step_start(WormholeProgramStep::VerifyPreviousNullifierProof);
+        if !withdrawal.withdrawal_index.is_zero() {
+            let previous_withdrawal_index = withdrawal.withdrawal_index - U256::from(1);
+            let previous_nullifier =
+                withdrawal.secret.nullifier_with_params(&input.params, previous_withdrawal_index);
+            let previous_nullifier_nibbles = Nibbles::unpack(keccak256(previous_nullifier));
+            let expected = alloy_rlp::encode_fixed_size(&cumulative_withdrawn_amount_hash(
+                withdrawal.cumulative_withdrawn_amount,
+            ))
+            .to_vec();
+            verify_proof(
+                nullifier_account.storage_root,
+                previous_nullifier_nibbles,
+                Some(expected),
+                &withdrawal.previous_nullifier_storage_proof,
+            )?;
+        }
+        observer.on_step_end(WormholeProgramStep::VerifyPreviousNullifierProof);
 
-    // Compute current nullifier to commit to.
-    let current_nullifier = input.secret.nullifier(input.withdrawal_index);
+        // Compute current nullifier to commit to along with the cumulative withdrawn amount
+        // including the withdrawal, which the next withdrawal starts from. The amount is also
+        // committed in encrypted form, so that it can be recovered from the chain state.
+        observer.on_step_start(WormholeProgramStep::ComputeNullifier);
+        let nullifier =
+            withdrawal.secret.nullifier_with_params(&input.params, withdrawal.withdrawal_index);
+        if nullifiers.iter().any(|entry| entry.nullifier == nullifier) {
+            return Err(WormholeProgramError::DuplicateNullifier);
+        }
+        nullifiers.push(WormholeNullifierOutput {
+            nullifier,
+            cumulative_withdrawn_amount_hashed: cumulative_withdrawn_amount_hash(
+                next_cumulative_withdrawn_amount,
+            ),
+            cumulative_withdrawn_amount_encrypted: withdrawal
+                .secret
+                .encrypt_cumulative_withdrawn_amount(nullifier, next_cumulative_withdrawn_amount),
+        });
+        observer.on_step_end(WormholeProgramStep::ComputeNullifier);
+    }
 
     // Return the program output.
     Ok(WormholeProgramOutput {
         nullifier_address: input.nullifier_address,
         state_root: input.state_root,
-        withdraw_amount: input.withdraw_amount,
-        current_nullifier,
-        cumulative_withdrawn_amount_hashed,
+        withdraw_amount,
+        nullifiers,
+        recipient: input.recipient,
+        relayer: input.relayer,
+        relayer_fee: input.relayer_fee,
+        params: input.params,
     })
 }
 
+/// The step of the Wormhole program execution.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum WormholeProgramStep {
+    /// Secret validation.
+    ValidateSecret,
+    /// Withdraw amount validation.
+    ValidateWithdrawAmount,
+    /// Withdrawal recipient validation.
+    ValidateRecipient,
+    /// Relayer fee validation.
+    ValidateRelayerFee,
+    /// Withdrawal index consistency validation.
+    ValidateWithdrawalIndex,
+    /// Deposit account state proof verification.
+    VerifyDepositAccountProof,
+    /// Nullifier account state proof verification.
+    VerifyNullifierAccountProof,
+    /// Previous nullifier inclusion storage proof verification.
+    VerifyPreviousNullifierProof,
+    /// Current nullifier computation.
+    ComputeNullifier,
+}
+
+impl fmt::Display for WormholeProgramStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ValidateSecret => write!(f, "validate secret"),
+            Self::ValidateWithdrawAmount => write!(f, "validate withdraw amount"),
+            Self::ValidateRecipient => write!(f, "validate recipient"),
+            Self::ValidateRelayerFee => write!(f, "validate relayer fee"),
+            Self::ValidateWithdrawalIndex => write!(f, "validate withdrawal index"),
+            Self::VerifyDepositAccountProof => write!(f, "verify deposit account proof"),
+            Self::VerifyNullifierAccountProof => write!(f, "verify nullifier account proof"),
+            Self::VerifyPreviousNullifierProof => write!(f, "verify previous nullifier proof"),
+            Self::ComputeNullifier => write!(f, "compute nullifier"),
+        }
+    }
+}
+
+/// The observer of the Wormhole program execution.
+///
+/// If the step fails, the program returns without notifying the observer about its end.
+pub trait WormholeProgramObserver {
+    /// Called before the step is executed.
+    fn on_step_start(&mut self, _step: WormholeProgramStep) {}
+
+    /// Called after the step has been successfully executed.
+    fn on_step_end(&mut self, _step: WormholeProgramStep) {}
+}
+
+impl WormholeProgramObserver for () {}
+
 /// The input into zkvm program.
-#[derive(PartialEq, Eq, Clone, Debug)]
+///
+/// The [`Debug`](fmt::Debug) output only includes the public inputs and the secret is zeroized on
+/// drop.
+#[derive(PartialEq, Eq, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(test, derive(Default))]
 pub struct WormholeProgramInput {
     /// The Wormhole secret.
+    #[cfg_attr(feature = "serde", serde(with = "alloy_wormhole::secret::serde_secret"))]
     pub secret: WormholeSecret,
     /// The deposit (burn) amount.
     pub deposit_amount: U256,
@@ -156,33 +340,202 @@ pub struct WormholeProgramInput {
     /// The inclusion storage proof of previous nullifier.
     /// Must be empty if withdrawal index is zero.
     pub previous_nullifier_storage_proof: Vec<Bytes>,
+    /// The recipient of the withdrawn (minted) amount.
+    pub recipient: Address,
+    /// The relayer submitting the withdrawal on behalf of the recipient.
+    /// Must be set if relayer fee is non-zero.
+    pub relayer: Address,
+    /// The fee paid to the relayer out of the withdraw amount.
+    pub relayer_fee: U256,
+    /// The Wormhole protocol parameters.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub params: WormholeParams,
 }
 
-/// The output of the zkvm program.
-#[derive(PartialEq, Eq, Clone, Debug)]
+impl fmt::Debug for WormholeProgramInput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WormholeProgramInput")
+            .field("withdraw_amount", &self.withdraw_amount)
+            .field("state_root", &self.state_root)
+            .field("nullifier_address", &self.nullifier_address)
+            .field("recipient", &self.recipient)
+            .field("relayer", &self.relayer)
+            .field("relayer_fee", &self.relayer_fee)
+            .field("params", &self.params)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The input into zkvm program for the batch of withdrawals against the same state root.
+///
+/// The [`Debug`](fmt::Debug) output only includes the public inputs and the secrets are zeroized
+/// on drop.
+#[derive(PartialEq, Eq, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct WormholeProgramOutput {
+#[cfg_attr(test, derive(Default))]
+pub struct WormholeBatchProgramInput {
+    /// The state root of the block to validate against.
+    pub state_root: B256,
     /// The address of the nullifier system contract.
     pub nullifier_address: Address,
-    /// The state root of the block to validate against provided as part of the input.
-    pub state_root: B256,
-    /// The withdraw amount provided as part of the input.
+    /// The Wormhole nullifier contract account proof.
+    pub nullifier_account_proof: Vec<Bytes>,
+    /// The recipient of the total withdrawn (minted) amount.
+    pub recipient: Address,
+    /// The relayer submitting the withdrawal on behalf of the recipient.
+    /// Must be set if relayer fee is non-zero.
+    pub relayer: Address,
+    /// The fee paid to the relayer out of the total withdraw amount.
+    pub relayer_fee: U256,
+    /// The Wormhole protocol parameters.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub params: WormholeParams,
+    /// The withdrawals from individual burn addresses.
+    pub withdrawals: Vec<WormholeWithdrawalInput>,
+}
+
+impl WormholeBatchProgramInput {
+    /// Creates the batch input from the inputs of individual withdrawals.
+    ///
+    /// All inputs must share the state root, the nullifier address, the recipient, the relayer
+    /// and the parameters. The relayer fee of the batch is the sum of the relayer fees.
+    pub fn from_inputs(
+        inputs: impl IntoIterator<Item = WormholeProgramInput>,
+    ) -> Result<Self, WormholeProgramError> {
+        let mut inputs = inputs.into_iter();
+        let mut batch = Self::from(inputs.next().ok_or(WormholeProgramError::EmptyBatch)?);
+        for input in inputs {
+            if input.state_root != batch.state_root ||
+                input.nullifier_address != batch.nullifier_address ||
+                input.recipient != batch.recipient ||
+                input.relayer != batch.relayer ||
+                input.params != batch.params
+            {
+                return Err(WormholeProgramError::BatchInputMismatch);
+            }
+            batch.relayer_fee = batch
+                .relayer_fee
+                .checked_add(input.relayer_fee)
+                .ok_or(WormholeProgramError::InvalidRelayerFee)?;
+            batch.withdrawals.extend(Self::from(input).withdrawals);
+        }
+        Ok(batch)
+    }
+}
+
+impl From<WormholeProgramInput> for WormholeBatchProgramInput {
+    fn from(input: WormholeProgramInput) -> Self {
+        let WormholeProgramInput {
+            secret,
+            deposit_amount,
+            withdraw_amount,
+            cumulative_withdrawn_amount,
+            withdrawal_index,
+            state_root,
+            deposit_account_proof,
+            nullifier_address,
+            nullifier_account_proof,
+            previous_nullifier_storage_proof,
+            recipient,
+            relayer,
+            relayer_fee,
+            params,
+        } = input;
+        Self {
+            state_root,
+            nullifier_address,
+            nullifier_account_proof,
+            recipient,
+            relayer,
+            relayer_fee,
+            params,
+            withdrawals: Vec::from([WormholeWithdrawalInput {
+                secret,
+                deposit_amount,
+                withdraw_amount,
+                cumulative_withdrawn_amount,
+                withdrawal_index,
+                deposit_account_proof,
+                previous_nullifier_storage_proof,
+            }]),
+        }
+    }
+}
+
+impl fmt::Debug for WormholeBatchProgramInput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WormholeBatchProgramInput")
+            .field("state_root", &self.state_root)
+            .field("nullifier_address", &self.nullifier_address)
+            .field("recipient", &self.recipient)
+            .field("relayer", &self.relayer)
+            .field("relayer_fee", &self.relayer_fee)
+            .field("params", &self.params)
+            .field("withdrawals", &self.withdrawals.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// The private input of a single withdrawal in [`WormholeBatchProgramInput`].
+///
+/// The [`Debug`](fmt::Debug) output is redacted and the secret is zeroized on drop.
+#[derive(PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(test, derive(Default))]
+pub struct WormholeWithdrawalInput {
+    /// The Wormhole secret.
+    #[cfg_attr(feature = "serde", serde(with = "alloy_wormhole::secret::serde_secret"))]
+    pub secret: WormholeSecret,
+    /// The deposit (burn) amount.
+    pub deposit_amount: U256,
+    /// The withdraw amount.
     pub withdraw_amount: U256,
-    /// The nullifier the withdrawal is for.
-    pub current_nullifier: B256,
-    /// The keccak256 of cumulative withdrawn amount.
-    pub cumulative_withdrawn_amount_hashed: B256,
+    /// The cumulative withdrawn amount.
+    pub cumulative_withdrawn_amount: U256,
+    /// The index of the current withdrawal.
+    pub withdrawal_index: U256,
+    /// The deposit account proof.
+    pub deposit_account_proof: Vec<Bytes>,
+    /// The inclusion storage proof of previous nullifier.
+    /// Must be empty if withdrawal index is zero.
+    pub previous_nullifier_storage_proof: Vec<Bytes>,
+}
+
+impl fmt::Debug for WormholeWithdrawalInput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WormholeWithdrawalInput").finish_non_exhaustive()
+    }
 }
 
 /// The error returned by Wormhole program.
 #[derive(PartialEq, Eq, Debug)]
 pub enum WormholeProgramError {
+    /// Provided secret is not [`SECRET_LENGTH`](alloy_wormhole::SECRET_LENGTH) bytes long.
+    InvalidSecretLength,
     /// Provided secret is not valid.
     InvalidSecret,
     /// The withdrawal amount is zero, overflows, or exceeds the deposited amount.
     InvalidWithdrawAmount,
+    /// The cumulative withdrawn amount exceeds the max deposit.
+    MaxDepositExceeded,
+    /// The withdrawal recipient is the zero address.
+    InvalidRecipient,
+    /// The relayer fee exceeds the withdraw amount or is set without a relayer.
+    InvalidRelayerFee,
+    /// The cumulative withdrawn amount is non-zero for the first withdrawal.
+    InconsistentFirstWithdrawal,
+    /// The previous nullifier storage proof is provided for the first withdrawal.
+    UnexpectedPreviousNullifierProof,
+    /// The nullifier account proof is empty.
+    EmptyNullifierAccountProof,
     /// The nullifier account proof does not contain a valid leaf.
     NullifierAccountMissing,
+    /// The batch does not contain any withdrawals.
+    EmptyBatch,
+    /// The inputs of the batched withdrawals do not share the public inputs.
+    BatchInputMismatch,
+    /// The batch contains multiple withdrawals with the same nullifier.
+    DuplicateNullifier,
     /// RLP decoding failure.
     Rlp(alloy_rlp::Error),
     /// Merkle-Patricia Trie proof verification failure.
@@ -194,9 +547,23 @@ impl core::error::Error for WormholeProgramError {}
 impl fmt::Display for WormholeProgramError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
+            Self::InvalidSecretLength => write!(f, "invalid secret length"),
             Self::InvalidSecret => write!(f, "invalid secret"),
             Self::InvalidWithdrawAmount => write!(f, "invalid withdraw amount"),
+            Self::MaxDepositExceeded => write!(f, "max deposit exceeded"),
+            Self::InvalidRecipient => write!(f, "invalid recipient"),
+            Self::InvalidRelayerFee => write!(f, "invalid relayer fee"),
+            Self::InconsistentFirstWithdrawal => {
+                write!(f, "cumulative withdrawn amount must be 0 for first withdrawal")
+            }
+            Self::UnexpectedPreviousNullifierProof => {
+                write!(f, "storage proof for previous nullifier must be empty for first withdrawal")
+            }
+            Self::EmptyNullifierAccountProof => write!(f, "empty nullifier account proof"),
             Self::NullifierAccountMissing => write!(f, "nullifier account missing"),
+            Self::EmptyBatch => write!(f, "empty batch"),
+            Self::BatchInputMismatch => write!(f, "batch input mismatch"),
+            Self::DuplicateNullifier => write!(f, "duplicate nullifier"),
             Self::Rlp(error) => write!(f, "rlp: {error}"),
             Self::Proof(error) => write!(f, "invalid proof: {error}"),
         }
@@ -218,20 +585,117 @@ impl From<ProofVerificationError> for WormholeProgramError {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use alloy_wormhole::secret::TEST_SECRET;
+    use alloy_trie::{proof::ProofRetainer, HashBuilder};
+    use alloy_wormhole::{secret::TEST_SECRET, MAX_DEPOSIT, SECRET_LENGTH};
+
+    const NULLIFIER_ADDRESS: Address = Address::with_last_byte(0x42);
+
+    /// Returns the root of the trie with provided leaves keyed by the hashed keys and the proof of
+    /// the target hashed key.
+    fn trie_proof(leaves: &[(B256, Vec<u8>)], target: B256) -> (B256, Vec<Bytes>) {
+        let mut leaves = leaves.to_vec();
+        leaves.sort_unstable_by_key(|(key, _)| *key);
+        let mut builder = HashBuilder::default()
+            .with_proof_retainer(ProofRetainer::from_iter([Nibbles::unpack(target)]));
+        for (key, value) in leaves {
+            builder.add_leaf(Nibbles::unpack(key), &value);
+        }
+        let root = builder.root();
+        let proof = builder
+            .take_proof_nodes()
+            .matching_nodes_sorted(&Nibbles::unpack(target))
+            .into_iter()
+            .map(|(_, node)| node)
+            .collect();
+        (root, proof)
+    }
+
+    /// Returns the input of the withdrawal from the burn address of [`TEST_SECRET`] with the
+    /// deposit of 10 against the state where the nullifier contract stores provided slots.
+    fn withdrawal_input(
+        withdrawal_index: u64,
+        cumulative_withdrawn_amount: U256,
+        withdraw_amount: U256,
+        storage: &[(B256, B256)],
+    ) -> WormholeProgramInput {
+        let params = WormholeParams::DEFAULT;
+        let withdrawal_index = U256::from(withdrawal_index);
+        let previous_nullifier = TEST_SECRET
+            .nullifier_with_params(&params, withdrawal_index.saturating_sub(U256::from(1)));
+        let storage =
+            Vec::from_iter(storage.iter().map(|(slot, value)| {
+                (keccak256(slot), alloy_rlp::encode_fixed_size(value).to_vec())
+            }));
+        let (storage_root, previous_nullifier_storage_proof) =
+            trie_proof(&storage, keccak256(previous_nullifier));
+
+        let deposit_address = TEST_SECRET.burn_address_with_params(&params);
+        let deposit_amount = U256::from(10);
+        let accounts = [
+            (
+                keccak256(deposit_address),
+                alloy_rlp::encode(TrieAccount { balance: deposit_amount, ..Default::default() }),
+            ),
+            (
+                keccak256(NULLIFIER_ADDRESS),
+                alloy_rlp::encode(TrieAccount { storage_root, ..Default::default() }),
+            ),
+        ];
+        let (state_root, deposit_account_proof) = trie_proof(&accounts, keccak256(deposit_address));
+        let (_, nullifier_account_proof) = trie_proof(&accounts, keccak256(NULLIFIER_ADDRESS));
+
+        WormholeProgramInput {
+            secret: TEST_SECRET,
+            deposit_amount,
+            withdraw_amount,
+            cumulative_withdrawn_amount,
+            withdrawal_index,
+            state_root,
+            deposit_account_proof,
+            nullifier_address: NULLIFIER_ADDRESS,
+            nullifier_account_proof,
+            previous_nullifier_storage_proof: if withdrawal_index.is_zero() {
+                Vec::new()
+            } else {
+                previous_nullifier_storage_proof
+            },
+            recipient: Address::with_last_byte(1),
+            params,
+            ..Default::default()
+        }
+    }
 
     #[test]
     fn invalid_secret() {
         let mut input = WormholeProgramInput::default();
         assert_eq!(
             execute_wormhole_program(input.clone()),
-            Err(WormholeProgramError::InvalidSecret)
+            Err(WormholeProgramError::InvalidSecretLength)
         );
 
         input.secret = WormholeSecret::new_unchecked(Bytes::from_static(&[0x1, 0x2, 0x3]));
+        assert_eq!(
+            execute_wormhole_program(input.clone()),
+            Err(WormholeProgramError::InvalidSecretLength)
+        );
+
+        input.secret = WormholeSecret::new_unchecked(Bytes::from_static(&[0x1; SECRET_LENGTH]));
         assert_eq!(execute_wormhole_program(input), Err(WormholeProgramError::InvalidSecret));
     }
 
+    #[test]
+    fn redacted_input_debug() {
+        let input = WormholeProgramInput {
+            secret: TEST_SECRET,
+            deposit_amount: U256::from(123456789),
+            ..Default::default()
+        };
+        let debug = alloc::format!("{input:?}");
+        assert!(!debug.contains("secret"));
+        assert!(!debug.contains("deposit_amount"));
+        assert!(debug.contains("withdraw_amount"));
+    }
+
     #[test]
     fn invalid_withdraw_amount() {
         let mut input = WormholeProgramInput { secret: TEST_SECRET, ..Default::default() };
@@ -256,4 +720,308 @@ mod tests {
             Err(WormholeProgramError::InvalidWithdrawAmount)
         );
     }
+
+    #[test]
+    fn max_deposit_exceeded() {
+        let mut input = WormholeProgramInput {
+            secret: TEST_SECRET,
+            deposit_amount: MAX_DEPOSIT + U256::from(1),
+            withdraw_amount: MAX_DEPOSIT + U256::from(1),
+            ..Default::default()
+        };
+        assert_eq!(
+            execute_wormhole_program(input.clone()),
+            Err(WormholeProgramError::MaxDepositExceeded)
+        );
+
+        input.withdraw_amount = U256::from(1);
+        input.cumulative_withdrawn_amount = MAX_DEPOSIT;
+        assert_eq!(
+            execute_wormhole_program(input.clone()),
+            Err(WormholeProgramError::MaxDepositExceeded)
+        );
+
+        // The amount up to the max deposit can be withdrawn.
+        input.withdraw_amount = MAX_DEPOSIT;
+        input.cumulative_withdrawn_amount = U256::ZERO;
+        assert_eq!(execute_wormhole_program(input), Err(WormholeProgramError::InvalidRecipient));
+    }
+
+    #[test]
+    fn custom_params() {
+        let secret = WormholeSecret::new_unchecked(Bytes::from_static(&[0x1; SECRET_LENGTH]));
+        let mut input = WormholeProgramInput {
+            secret,
+            deposit_amount: U256::from(2),
+            withdraw_amount: U256::from(2),
+            ..Default::default()
+        };
+        assert_eq!(
+            execute_wormhole_program(input.clone()),
+            Err(WormholeProgramError::InvalidSecret)
+        );
+
+        input.params = WormholeParams::DEFAULT.with_pow_log_difficulty(0);
+        assert_eq!(
+            execute_wormhole_program(input.clone()),
+            Err(WormholeProgramError::InvalidRecipient)
+        );
+
+        input.params = input.params.with_max_deposit(U256::from(1));
+        assert_eq!(execute_wormhole_program(input), Err(WormholeProgramError::MaxDepositExceeded));
+    }
+
+    #[test]
+    fn invalid_recipient() {
+        let input = WormholeProgramInput {
+            secret: TEST_SECRET,
+            deposit_amount: U256::from(1),
+            withdraw_amount: U256::from(1),
+            ..Default::default()
+        };
+        assert_eq!(execute_wormhole_program(input), Err(WormholeProgramError::InvalidRecipient));
+    }
+
+    #[test]
+    fn invalid_relayer_fee() {
+        let mut input = WormholeProgramInput {
+            secret: TEST_SECRET,
+            deposit_amount: U256::from(2),
+            withdraw_amount: U256::from(1),
+            recipient: Address::with_last_byte(1),
+            relayer_fee: U256::from(1),
+            ..Default::default()
+        };
+        assert_eq!(
+            execute_wormhole_program(input.clone()),
+            Err(WormholeProgramError::InvalidRelayerFee)
+        );
+
+        input.relayer = Address::with_last_byte(2);
+        input.relayer_fee = U256::from(2);
+        assert_eq!(execute_wormhole_program(input), Err(WormholeProgramError::InvalidRelayerFee));
+    }
+
+    #[test]
+    fn inconsistent_first_withdrawal() {
+        let mut input = WormholeProgramInput {
+            secret: TEST_SECRET,
+            deposit_amount: U256::from(2),
+            withdraw_amount: U256::from(1),
+            cumulative_withdrawn_amount: U256::from(1),
+            recipient: Address::with_last_byte(1),
+            ..Default::default()
+        };
+        assert_eq!(
+            execute_wormhole_program(input.clone()),
+            Err(WormholeProgramError::InconsistentFirstWithdrawal)
+        );
+
+        input.cumulative_withdrawn_amount = U256::ZERO;
+        input.previous_nullifier_storage_proof = alloc::vec![Bytes::from_static(&[0x1])];
+        assert_eq!(
+            execute_wormhole_program(input),
+            Err(WormholeProgramError::UnexpectedPreviousNullifierProof)
+        );
+    }
+
+    #[test]
+    fn empty_nullifier_account_proof() {
+        let mut input = WormholeProgramInput {
+            secret: TEST_SECRET,
+            deposit_amount: U256::from(2),
+            withdraw_amount: U256::from(1),
+            recipient: Address::with_last_byte(1),
+            ..Default::default()
+        };
+        assert_eq!(
+            execute_wormhole_program(input.clone()),
+            Err(WormholeProgramError::EmptyNullifierAccountProof)
+        );
+
+        input.withdrawal_index = U256::from(1);
+        input.cumulative_withdrawn_amount = U256::from(1);
+        assert_eq!(
+            execute_wormhole_program(input),
+            Err(WormholeProgramError::EmptyNullifierAccountProof)
+        );
+    }
+
+    #[test]
+    fn batch_from_inputs() {
+        let input = WormholeProgramInput {
+            secret: TEST_SECRET,
+            withdraw_amount: U256::from(1),
+            recipient: Address::with_last_byte(1),
+            relayer: Address::with_last_byte(2),
+            relayer_fee: U256::from(1),
+            ..Default::default()
+        };
+        assert_eq!(
+            WormholeBatchProgramInput::from_inputs([]),
+            Err(WormholeProgramError::EmptyBatch)
+        );
+
+        let batch = WormholeBatchProgramInput::from_inputs([input.clone(), input.clone()]).unwrap();
+        assert_eq!(batch.withdrawals.len(), 2);
+        assert_eq!(batch.relayer_fee, U256::from(2));
+        assert_eq!(batch.recipient, input.recipient);
+
+        let other = WormholeProgramInput { state_root: B256::with_last_byte(1), ..input.clone() };
+        assert_eq!(
+            WormholeBatchProgramInput::from_inputs([input, other]),
+            Err(WormholeProgramError::BatchInputMismatch)
+        );
+    }
+
+    #[test]
+    fn batch_program() {
+        assert_eq!(
+            execute_wormhole_batch_program(WormholeBatchProgramInput::default()),
+            Err(WormholeProgramError::EmptyBatch)
+        );
+
+        let withdrawal = WormholeWithdrawalInput {
+            secret: TEST_SECRET,
+            deposit_amount: U256::from(1),
+            withdraw_amount: U256::from(1),
+            ..Default::default()
+        };
+        let mut input = WormholeBatchProgramInput {
+            recipient: Address::with_last_byte(1),
+            relayer: Address::with_last_byte(2),
+            relayer_fee: U256::from(2),
+            withdrawals: alloc::vec![withdrawal.clone()],
+            ..Default::default()
+        };
+        assert_eq!(
+            execute_wormhole_batch_program(input.clone()),
+            Err(WormholeProgramError::InvalidRelayerFee)
+        );
+
+        // The relayer fee is validated against the total withdraw amount.
+        input.withdrawals.push(withdrawal.clone());
+        assert_eq!(
+            execute_wormhole_batch_program(input.clone()),
+            Err(WormholeProgramError::EmptyNullifierAccountProof)
+        );
+
+        // Each withdrawal is validated individually.
+        input
+            .withdrawals
+            .push(WormholeWithdrawalInput { withdraw_amount: U256::ZERO, ..withdrawal });
+        assert_eq!(
+            execute_wormhole_batch_program(input),
+            Err(WormholeProgramError::InvalidWithdrawAmount)
+        );
+    }
+
+    #[test]
+    fn observe_steps() {
+        #[derive(Default)]
+        struct Recorder {
+            started: Vec<WormholeProgramStep>,
+            ended: Vec<WormholeProgramStep>,
+        }
+
+        impl WormholeProgramObserver for Recorder {
+            fn on_step_start(&mut self, step: WormholeProgramStep) {
+                self.started.push(step);
+            }
+
+            fn on_step_end(&mut self, step: WormholeProgramStep) {
+                self.ended.push(step);
+            }
+        }
+
+        let input = WormholeProgramInput {
+            secret: TEST_SECRET,
+            deposit_amount: U256::from(1),
+            withdraw_amount: U256::from(1),
+            ..Default::default()
+        };
+        let mut recorder = Recorder::default();
+        assert_eq!(
+            execute_wormhole_program_with_observer(input, &mut recorder),
+            Err(WormholeProgramError::InvalidRecipient)
+        );
+        assert_eq!(
+            recorder.started,
+            [
+                WormholeProgramStep::ValidateSecret,
+                WormholeProgramStep::ValidateWithdrawAmount,
+                WormholeProgramStep::ValidateRecipient
+            ]
+        );
+        assert_eq!(
+            recorder.ended,
+            [WormholeProgramStep::ValidateSecret, WormholeProgramStep::ValidateWithdrawAmount]
+        );
+    }
+
+    #[test]
+    fn commit_cumulative_withdrawn_amount() {
+        let first = U256::from(3);
+        let output = execute_wormhole_program(withdrawal_input(0, U256::ZERO, first, &[])).unwrap();
+        let committed = &output.nullifiers[0];
+        assert_eq!(committed.nullifier, TEST_SECRET.nullifier(U256::ZERO));
+        assert_eq!(
+            committed.cumulative_withdrawn_amount_hashed,
+            cumulative_withdrawn_amount_hash(U256::ZERO + first)
+        );
+
+        // The next withdrawal starts from the cumulative withdrawn amount committed by the
+        // previous one.
+        let second = U256::from(4);
+        let storage = [(committed.nullifier, committed.cumulative_withdrawn_amount_hashed)];
+        let output =
+            execute_wormhole_program(withdrawal_input(1, first, second, &storage)).unwrap();
+        assert_eq!(
+            output.nullifiers[0].cumulative_withdrawn_amount_hashed,
+            cumulative_withdrawn_amount_hash(first + second)
+        );
+
+        // The deposit can't be withdrawn again from the amount before the previous withdrawal.
+        assert!(matches!(
+            execute_wormhole_program(withdrawal_input(1, U256::ZERO, U256::from(10), &storage)),
+            Err(WormholeProgramError::Proof(_))
+        ));
+
+        // Under the previous semantics, the nullifier slot stores the cumulative withdrawn amount
+        // before the withdrawal, which the next withdrawal can't start from.
+        let storage = [(committed.nullifier, cumulative_withdrawn_amount_hash(U256::ZERO))];
+        assert!(matches!(
+            execute_wormhole_program(withdrawal_input(1, first, second, &storage)),
+            Err(WormholeProgramError::Proof(_))
+        ));
+    }
+
+    #[test]
+    fn commit_encrypted_cumulative_withdrawn_amount() {
+        let cumulative_withdrawn_amount = U256::from(3);
+        let withdraw_amount = U256::from(4);
+        let storage = [(
+            TEST_SECRET.nullifier(U256::ZERO),
+            cumulative_withdrawn_amount_hash(cumulative_withdrawn_amount),
+        )];
+        let output = execute_wormhole_program(withdrawal_input(
+            1,
+            cumulative_withdrawn_amount,
+            withdraw_amount,
+            &storage,
+        ))
+        .unwrap();
+        let committed = &output.nullifiers[0];
+        assert_eq!(
+            TEST_SECRET.decrypt_cumulative_withdrawn_amount(
+                committed.nullifier,
+                committed.cumulative_withdrawn_amount_encrypted
+            ),
+            cumulative_withdrawn_amount + withdraw_amount
+        );
+        assert_ne!(
+            committed.cumulative_withdrawn_amount_encrypted,
+            B256::new((cumulative_withdrawn_amount + withdraw_amount).to_be_bytes())
+        );
+    }
 }
@@ -0,0 +1,120 @@
+//! Verifiers of the Wormhole program proofs.
+
+use alloy_primitives::B256;
+use alloy_wormhole::WormholeProofSystem;
+use core::fmt;
+use wormhole_program_core::WormholeProgramOutput;
+
+mod registry;
+pub use registry::WormholeProgramRegistry;
+
+#[cfg(feature = "sp1")]
+mod sp1;
+#[cfg(feature = "sp1")]
+pub use sp1::Sp1Groth16Verifier;
+
+#[cfg(feature = "risc0")]
+mod risc0;
+#[cfg(feature = "risc0")]
+pub use risc0::Risc0ReceiptVerifier;
+
+/// The verifier of the Wormhole program proofs.
+pub trait WormholeProofVerifier {
+    /// Returns the proof system this verifier accepts proofs of.
+    fn proof_system(&self) -> WormholeProofSystem;
+
+    /// Returns the commitment to the program this verifier accepts proofs of,
+    /// i.e. the verifying key hash or the image ID.
+    fn program_id(&self) -> B256;
+
+    /// Verifies that the proof attests to the Wormhole program execution with the expected output.
+    ///
+    /// Returns `Ok(())` if the proof is accepted.
+    fn verify(
+        &self,
+        proof: &[u8],
+        expected: &WormholeProgramOutput,
+    ) -> Result<(), WormholeProofVerifierError>;
+}
+
+impl<T: WormholeProofVerifier + ?Sized> WormholeProofVerifier for &T {
+    fn proof_system(&self) -> WormholeProofSystem {
+        (**self).proof_system()
+    }
+
+    fn program_id(&self) -> B256 {
+        (**self).program_id()
+    }
+
+    fn verify(
+        &self,
+        proof: &[u8],
+        expected: &WormholeProgramOutput,
+    ) -> Result<(), WormholeProofVerifierError> {
+        (**self).verify(proof, expected)
+    }
+}
+
+impl<T: WormholeProofVerifier + ?Sized> WormholeProofVerifier for Box<T> {
+    fn proof_system(&self) -> WormholeProofSystem {
+        (**self).proof_system()
+    }
+
+    fn program_id(&self) -> B256 {
+        (**self).program_id()
+    }
+
+    fn verify(
+        &self,
+        proof: &[u8],
+        expected: &WormholeProgramOutput,
+    ) -> Result<(), WormholeProofVerifierError> {
+        (**self).verify(proof, expected)
+    }
+}
+
+/// The error returned by [`WormholeProofVerifier`] if the proof is rejected.
+#[derive(Debug)]
+pub enum WormholeProofVerifierError {
+    /// The program is not registered.
+    UnknownProgram(WormholeProofSystem, B256),
+    /// The program is deprecated.
+    DeprecatedProgram(WormholeProofSystem, B256),
+    /// The proof could not be decoded.
+    Decode(bincode::Error),
+    /// The public values committed to by the proof do not match the expected program output.
+    OutputMismatch,
+    /// The proof verification failed.
+    InvalidProof(Box<dyn core::error::Error + Send + Sync>),
+}
+
+impl core::error::Error for WormholeProofVerifierError {}
+
+impl fmt::Display for WormholeProofVerifierError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownProgram(proof_system, program_id) => {
+                write!(f, "unknown program {program_id} ({proof_system})")
+            }
+            Self::DeprecatedProgram(proof_system, program_id) => {
+                write!(f, "deprecated program {program_id} ({proof_system})")
+            }
+            Self::Decode(error) => write!(f, "decode: {error}"),
+            Self::OutputMismatch => write!(f, "program output mismatch"),
+            Self::InvalidProof(error) => write!(f, "invalid proof: {error}"),
+        }
+    }
+}
+
+impl WormholeProofVerifierError {
+    /// Creates new [`WormholeProofVerifierError::InvalidProof`] error.
+    pub fn invalid_proof(error: impl core::error::Error + Send + Sync + 'static) -> Self {
+        Self::InvalidProof(Box::new(error))
+    }
+}
+
+impl From<bincode::Error> for WormholeProofVerifierError {
+    fn from(error: bincode::Error) -> Self {
+        Self::Decode(error)
+    }
+}
@@ -0,0 +1,193 @@
+use crate::{WormholeProofVerifier, WormholeProofVerifierError};
+use alloy_primitives::B256;
+use alloy_wormhole::{WormholeProofSystem, WormholeTxProof, WormholeTxProofVerifier};
+use core::fmt;
+use std::collections::HashMap;
+use wormhole_program_core::WormholeProgramOutput;
+
+/// The registry of supported Wormhole programs.
+///
+/// Dispatches the proof verification to the verifier registered for the proof system and program
+/// ID the proof is tagged with. Proofs of unknown or deprecated programs are rejected.
+#[derive(Default)]
+pub struct WormholeProgramRegistry {
+    programs: HashMap<(WormholeProofSystem, B256), RegisteredProgram>,
+}
+
+struct RegisteredProgram {
+    verifier: Box<dyn WormholeProofVerifier + Send + Sync>,
+    deprecated: bool,
+}
+
+impl fmt::Debug for WormholeProgramRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.programs.iter().map(|(key, program)| (key, program.deprecated)))
+            .finish()
+    }
+}
+
+impl WormholeProgramRegistry {
+    /// Registers the verifier for its proof system and program ID.
+    /// Replaces any previously registered verifier for the same program.
+    pub fn register<V>(&mut self, verifier: V) -> &mut Self
+    where
+        V: WormholeProofVerifier + Send + Sync + 'static,
+    {
+        let key = (verifier.proof_system(), verifier.program_id());
+        self.programs
+            .insert(key, RegisteredProgram { verifier: Box::new(verifier), deprecated: false });
+        self
+    }
+
+    /// Marks the program as deprecated. Proofs of deprecated programs are rejected.
+    ///
+    /// Returns `false` if the program is not registered.
+    pub fn deprecate(&mut self, proof_system: WormholeProofSystem, program_id: B256) -> bool {
+        if let Some(program) = self.programs.get_mut(&(proof_system, program_id)) {
+            program.deprecated = true;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns `true` if the program is registered and not deprecated.
+    pub fn is_supported(&self, proof_system: WormholeProofSystem, program_id: B256) -> bool {
+        self.programs.get(&(proof_system, program_id)).is_some_and(|program| !program.deprecated)
+    }
+
+    /// Verifies the proof with the verifier registered for provided proof system and program ID.
+    pub fn verify(
+        &self,
+        proof_system: WormholeProofSystem,
+        program_id: B256,
+        proof: &[u8],
+        expected: &WormholeProgramOutput,
+    ) -> Result<(), WormholeProofVerifierError> {
+        let program = self
+            .programs
+            .get(&(proof_system, program_id))
+            .ok_or(WormholeProofVerifierError::UnknownProgram(proof_system, program_id))?;
+        if program.deprecated {
+            return Err(WormholeProofVerifierError::DeprecatedProgram(proof_system, program_id));
+        }
+        program.verifier.verify(proof, expected)
+    }
+}
+
+impl WormholeTxProofVerifier for WormholeProgramRegistry {
+    type Error = WormholeProofVerifierError;
+
+    fn verify_tx_proof(&self, proof: &WormholeTxProof) -> Result<(), Self::Error> {
+        self.verify(proof.proof_system, proof.program_id, &proof.proof, &proof.output())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{Address, Bytes, U256};
+
+    struct MockVerifier;
+
+    impl WormholeProofVerifier for MockVerifier {
+        fn proof_system(&self) -> WormholeProofSystem {
+            WormholeProofSystem::SP1_V4_GROTH16
+        }
+
+        fn program_id(&self) -> B256 {
+            B256::with_last_byte(1)
+        }
+
+        fn verify(
+            &self,
+            proof: &[u8],
+            _expected: &WormholeProgramOutput,
+        ) -> Result<(), WormholeProofVerifierError> {
+            if proof.is_empty() {
+                Err(WormholeProofVerifierError::OutputMismatch)
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    fn output() -> WormholeProgramOutput {
+        WormholeProgramOutput {
+            withdraw_amount: U256::from(1),
+            recipient: Address::with_last_byte(1),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn verify_registered_program() {
+        let mut registry = WormholeProgramRegistry::default();
+        registry.register(MockVerifier);
+
+        let proof_system = WormholeProofSystem::SP1_V4_GROTH16;
+        let program_id = B256::with_last_byte(1);
+        assert!(registry.is_supported(proof_system, program_id));
+        assert!(registry.verify(proof_system, program_id, &[0x1], &output()).is_ok());
+        assert!(matches!(
+            registry.verify(proof_system, program_id, &[], &output()),
+            Err(WormholeProofVerifierError::OutputMismatch)
+        ));
+    }
+
+    #[test]
+    fn reject_unknown_program() {
+        let mut registry = WormholeProgramRegistry::default();
+        registry.register(MockVerifier);
+
+        for (proof_system, program_id) in [
+            (WormholeProofSystem::RISC0_V2, B256::with_last_byte(1)),
+            (WormholeProofSystem::SP1_V4_GROTH16, B256::with_last_byte(2)),
+            (WormholeProofSystem::new(0xff), B256::with_last_byte(1)),
+        ] {
+            assert!(!registry.is_supported(proof_system, program_id));
+            assert!(matches!(
+                registry.verify(proof_system, program_id, &[0x1], &output()),
+                Err(WormholeProofVerifierError::UnknownProgram(..))
+            ));
+        }
+    }
+
+    #[test]
+    fn reject_deprecated_program() {
+        let mut registry = WormholeProgramRegistry::default();
+        registry.register(MockVerifier);
+
+        let proof_system = WormholeProofSystem::SP1_V4_GROTH16;
+        let program_id = B256::with_last_byte(1);
+        assert!(registry.deprecate(proof_system, program_id));
+        assert!(!registry.deprecate(proof_system, B256::with_last_byte(2)));
+
+        assert!(!registry.is_supported(proof_system, program_id));
+        assert!(matches!(
+            registry.verify(proof_system, program_id, &[0x1], &output()),
+            Err(WormholeProofVerifierError::DeprecatedProgram(..))
+        ));
+    }
+
+    #[test]
+    fn verify_tx_proof() {
+        let mut registry = WormholeProgramRegistry::default();
+        registry.register(MockVerifier);
+
+        let mut proof = WormholeTxProof::new(
+            output(),
+            WormholeProofSystem::SP1_V4_GROTH16,
+            B256::with_last_byte(1),
+            Bytes::from_static(&[0x1]),
+        );
+        assert!(registry.verify_tx_proof(&proof).is_ok());
+
+        proof.proof_system = WormholeProofSystem::RISC0_V2;
+        assert!(matches!(
+            registry.verify_tx_proof(&proof),
+            Err(WormholeProofVerifierError::UnknownProgram(..))
+        ));
+    }
+}
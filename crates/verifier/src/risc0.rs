@@ -0,0 +1,50 @@
+use crate::{WormholeProofVerifier, WormholeProofVerifierError};
+use alloy_primitives::B256;
+use alloy_wormhole::WormholeProofSystem;
+use risc0_zkvm::{sha::Digest, Receipt};
+use wormhole_program_core::WormholeProgramOutput;
+
+/// The verifier of the Risc0 receipts of the Wormhole program.
+///
+/// The proof is expected to be a bincode serialized [`Receipt`].
+#[derive(Clone, Debug)]
+pub struct Risc0ReceiptVerifier {
+    /// The program image ID.
+    image_id: Digest,
+}
+
+impl Risc0ReceiptVerifier {
+    /// Creates new verifier for the program with provided image ID.
+    pub fn new(image_id: impl Into<Digest>) -> Self {
+        Self { image_id: image_id.into() }
+    }
+
+    /// Returns the program image ID.
+    pub fn image_id(&self) -> Digest {
+        self.image_id
+    }
+}
+
+impl WormholeProofVerifier for Risc0ReceiptVerifier {
+    fn proof_system(&self) -> WormholeProofSystem {
+        WormholeProofSystem::RISC0_V2
+    }
+
+    fn program_id(&self) -> B256 {
+        B256::from_slice(self.image_id.as_bytes())
+    }
+
+    fn verify(
+        &self,
+        proof: &[u8],
+        expected: &WormholeProgramOutput,
+    ) -> Result<(), WormholeProofVerifierError> {
+        let receipt: Receipt = bincode::deserialize(proof)?;
+
+        if receipt.journal.bytes != expected.public_values() {
+            return Err(WormholeProofVerifierError::OutputMismatch);
+        }
+
+        receipt.verify(self.image_id).map_err(WormholeProofVerifierError::invalid_proof)
+    }
+}
@@ -0,0 +1,48 @@
+use crate::{WormholeProofVerifier, WormholeProofVerifierError};
+use alloy_primitives::B256;
+use alloy_wormhole::WormholeProofSystem;
+use sp1_verifier::{Groth16Verifier, GROTH16_VK_BYTES};
+use wormhole_program_core::WormholeProgramOutput;
+
+/// The verifier of the SP1 Groth16 proofs of the Wormhole program.
+///
+/// The proof is expected to be encoded as returned by `SP1ProofWithPublicValues::bytes`.
+#[derive(Clone, Debug)]
+pub struct Sp1Groth16Verifier {
+    /// The hash of the program verifying key.
+    vkey_hash: B256,
+}
+
+impl Sp1Groth16Verifier {
+    /// Creates new verifier for the program with provided verifying key hash
+    /// as returned by `SP1VerifyingKey::bytes32_raw`.
+    pub fn new(vkey_hash: B256) -> Self {
+        Self { vkey_hash }
+    }
+
+    /// Returns the hash of the program verifying key.
+    pub fn vkey_hash(&self) -> B256 {
+        self.vkey_hash
+    }
+}
+
+impl WormholeProofVerifier for Sp1Groth16Verifier {
+    fn proof_system(&self) -> WormholeProofSystem {
+        WormholeProofSystem::SP1_V4_GROTH16
+    }
+
+    fn program_id(&self) -> B256 {
+        self.vkey_hash
+    }
+
+    fn verify(
+        &self,
+        proof: &[u8],
+        expected: &WormholeProgramOutput,
+    ) -> Result<(), WormholeProofVerifierError> {
+        let public_values = expected.public_values();
+        let vkey_hash = self.vkey_hash.to_string();
+        Groth16Verifier::verify(proof, &public_values, &vkey_hash, *GROTH16_VK_BYTES)
+            .map_err(WormholeProofVerifierError::invalid_proof)
+    }
+}
@@ -2,17 +2,17 @@
 
 #![no_main]
 
-use wormhole_program_core::{execute_wormhole_program, WormholeProgramInput};
+use wormhole_program_core::{execute_wormhole_batch_program, WormholeBatchProgramInput};
 
 pico_sdk::entrypoint!(main);
 
 pub fn main() {
     // Read input.
-    let input = pico_sdk::io::read_as::<WormholeProgramInput>();
+    let input = pico_sdk::io::read_as::<WormholeBatchProgramInput>();
 
     // Execute the program.
-    let output = execute_wormhole_program(input).expect("program success");
+    let output = execute_wormhole_batch_program(input).expect("program success");
 
     // Commit to the public values of the program.
-    pico_sdk::io::commit(&output);
+    pico_sdk::io::commit_bytes(&output.public_values());
 }
@@ -0,0 +1,36 @@
+//! The Risc0 program for aggregating the receipts of the Wormhole program.
+
+#![no_main]
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use risc0_zkvm::guest::env;
+use wormhole_program_core::{WormholeAggregationOutput, WormholeProgramOutput};
+
+risc0_zkvm::guest::entry!(main);
+
+fn main() {
+    // Read the image ID of the Wormhole program.
+    let image_id = env::read::<[u32; 8]>();
+
+    // Read the journals of the receipts to aggregate.
+    let journals = env::read::<Vec<Vec<u8>>>();
+
+    // Verify the receipts added as assumptions by the host and decode their outputs.
+    let outputs = journals
+        .iter()
+        .map(|journal| {
+            env::verify(image_id, journal.as_slice()).expect("valid receipt");
+            WormholeProgramOutput::from_public_values(journal).expect("valid journal")
+        })
+        .collect();
+
+    // Commit to the public values of the aggregation.
+    let output = WormholeAggregationOutput {
+        program_id: WormholeAggregationOutput::program_id_from_words(image_id),
+        outputs,
+    };
+    env::commit_slice(&output.public_values());
+}
@@ -3,17 +3,17 @@
 #![no_main]
 #![no_std]
 
-use wormhole_program_core::{execute_wormhole_program, WormholeProgramInput};
+use wormhole_program_core::{execute_wormhole_batch_program, WormholeBatchProgramInput};
 
 risc0_zkvm::guest::entry!(main);
 
 fn main() {
     // Read input.
-    let input = risc0_zkvm::guest::env::read::<WormholeProgramInput>();
+    let input = risc0_zkvm::guest::env::read::<WormholeBatchProgramInput>();
 
     // Execute the program.
-    let output = execute_wormhole_program(input).expect("program success");
+    let output = execute_wormhole_batch_program(input).expect("program success");
 
     // Commit to the public values of the program.
-    risc0_zkvm::guest::env::commit(&output);
+    risc0_zkvm::guest::env::commit_slice(&output.public_values());
 }
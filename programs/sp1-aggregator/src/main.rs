@@ -0,0 +1,33 @@
+//! The SP1 program for aggregating the proofs of the Wormhole program.
+
+#![no_main]
+
+use sha2::{Digest, Sha256};
+use wormhole_program_core::{WormholeAggregationOutput, WormholeProgramOutput};
+
+sp1_zkvm::entrypoint!(main);
+
+fn main() {
+    // Read the verifying key digest of the Wormhole program.
+    let vkey = sp1_zkvm::io::read::<[u32; 8]>();
+
+    // Read the public values of the proofs to aggregate.
+    let public_values = sp1_zkvm::io::read::<Vec<Vec<u8>>>();
+
+    // Verify the compressed proofs provided by the host and decode their outputs.
+    let outputs = public_values
+        .iter()
+        .map(|public_values| {
+            let digest = Sha256::digest(public_values);
+            sp1_zkvm::lib::verify::verify_sp1_proof(&vkey, &digest.into());
+            WormholeProgramOutput::from_public_values(public_values).expect("valid public values")
+        })
+        .collect();
+
+    // Commit to the public values of the aggregation.
+    let output = WormholeAggregationOutput {
+        program_id: WormholeAggregationOutput::program_id_from_words(vkey),
+        outputs,
+    };
+    sp1_zkvm::io::commit_slice(&output.public_values());
+}
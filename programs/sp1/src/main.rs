@@ -2,17 +2,17 @@
 
 #![no_main]
 
-use wormhole_program_core::{execute_wormhole_program, WormholeProgramInput};
+use wormhole_program_core::{execute_wormhole_batch_program, WormholeBatchProgramInput};
 
 sp1_zkvm::entrypoint!(main);
 
 fn main() {
     // Read input.
-    let input = sp1_zkvm::io::read::<WormholeProgramInput>();
+    let input = sp1_zkvm::io::read::<WormholeBatchProgramInput>();
 
     // Execute the program.
-    let output = execute_wormhole_program(input).expect("program success");
+    let output = execute_wormhole_batch_program(input).expect("program success");
 
     // Commit to the public values of the program.
-    sp1_zkvm::io::commit(&output);
+    sp1_zkvm::io::commit_slice(&output.public_values());
 }